@@ -1,37 +1,239 @@
+use crate::{
+    author::Author,
+    footnote::Footnote,
+    markdown::MarkdownRenderer,
+    plain_text::{TextOptions, TextRenderer},
+    resource::Resource,
+    save::{self, Collision, SaveError, SaveOptions},
+    standalone::{StandaloneHtml, StandaloneOptions},
+    statistics::Statistics,
+};
 use chrono::{DateTime, Utc};
-use std::fs::File;
-use std::io::{Error, ErrorKind, Write};
-use std::path::PathBuf;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
 use url::Url;
 
+// files & folders written by `save` and `save_bundle`: names must be unique across both
+const SAVE_TARGETS: &[&str] = &["{}.html", "{}.json", "{}"];
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Article {
     pub title: Option<String>,
+    /// Names of all `authors` separated by `, `
     pub author: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub authors: Vec<Author>,
     pub url: Url,
+    /// `<link rel="canonical">` or `og:url`
+    pub canonical_url: Option<Url>,
+    /// `<link rel="amphtml">`
+    pub amp_url: Option<Url>,
+    /// Printer friendly version of the article
+    pub print_url: Option<Url>,
+    /// Date the article was first published
     pub date: Option<DateTime<Utc>>,
+    /// Date of the last update of the article. Never older than `date`.
+    pub date_modified: Option<DateTime<Utc>>,
     pub thumbnail_url: Option<String>,
+    /// Summary from the page metadata or the first substantial paragraph
+    pub description: Option<String>,
+    /// Name of the publication or website
+    pub site_name: Option<String>,
+    /// Language of the article as BCP 47 tag, e.g. `en-US`
+    pub language: Option<String>,
+    /// Text direction: `ltr`, `rtl` or `auto`
+    pub direction: Option<String>,
+    /// Topics and keywords, deduplicated case-insensitively
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: Vec<String>,
+    /// Section of the publication the article appeared in
+    pub section: Option<String>,
     pub html: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub footnotes: Vec<Footnote>,
+    /// Statistics of the extracted content
+    pub statistics: Option<Statistics>,
 }
 
 impl Article {
+    pub(crate) fn new(url: Url) -> Self {
+        Self {
+            title: None,
+            author: None,
+            authors: Vec::new(),
+            url,
+            canonical_url: None,
+            amp_url: None,
+            print_url: None,
+            date: None,
+            date_modified: None,
+            thumbnail_url: None,
+            description: None,
+            site_name: None,
+            language: None,
+            direction: None,
+            tags: Vec::new(),
+            section: None,
+            html: None,
+            footnotes: Vec::new(),
+            statistics: None,
+        }
+    }
+
+    /// Render the extracted HTML as plain text. See [`TextRenderer`].
+    pub fn to_text(&self, options: &TextOptions) -> Option<String> {
+        let html = self.html.as_deref()?;
+        TextRenderer::render(html, options).ok()
+    }
+
+    /// Convert the extracted HTML to Markdown. See [`MarkdownRenderer`].
+    pub fn to_markdown(&self) -> Option<String> {
+        let html = self.html.as_deref()?;
+        MarkdownRenderer::render(html).ok()
+    }
+
+    /// Render a self-contained HTML document with inlined images. See [`StandaloneHtml`].
+    pub fn to_standalone_html<R>(&self, options: &StandaloneOptions, resolver: R) -> Option<String>
+    where
+        R: Fn(&Url) -> Option<Resource>,
+    {
+        StandaloneHtml::render(self, options, resolver).ok()
+    }
+
+    /// Write the raw HTML to `<path>/<name>.html`, replacing an existing file of the same name.
+    /// The name is the slugified title like for [`Article::save`].
     pub fn save_html(&self, path: &PathBuf) -> Result<(), Error> {
-        if let Some(html) = self.html.as_deref() {
-            if let Ok(()) = std::fs::create_dir_all(path) {
-                let mut file_name = match self.title.clone() {
-                    Some(file_name) => file_name.replace('/', "_"),
-                    None => "Unknown Title".to_owned(),
-                };
-                file_name.push_str(".html");
-                let path = path.join(file_name);
-                let mut html_file = File::create(path)?;
-                html_file.write_all(html.as_bytes())?;
-                return Ok(());
+        let html = self
+            .html
+            .as_deref()
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "Article does not contain HTML"))?;
+        std::fs::create_dir_all(path)?;
+
+        let stem = save::available_stem(
+            path,
+            &SaveOptions::default().file_stem(self),
+            &["{}.html"],
+            Collision::Overwrite,
+        )
+        .map_err(|error| Error::other(error.to_string()))?;
+        std::fs::write(path.join(format!("{stem}.html")), html)
+    }
+
+    /// Write the HTML as standalone document to `<directory>/<name>.html` and return its path.
+    /// See [`SaveOptions`] for how the name is built. Unlike [`Article::save_html`], which
+    /// writes the bare content, existing files are not overwritten by default.
+    pub fn save(&self, directory: &Path, options: &SaveOptions) -> Result<PathBuf, SaveError> {
+        let html = self.html.as_deref().ok_or(SaveError::NoContent)?;
+        std::fs::create_dir_all(directory)?;
+
+        let stem = save::available_stem(
+            directory,
+            &options.file_stem(self),
+            SAVE_TARGETS,
+            options.collision,
+        )?;
+        let path = directory.join(format!("{stem}.html"));
+        std::fs::write(&path, StandaloneHtml::document(self, html, None))?;
+
+        #[cfg(feature = "serde")]
+        if options.metadata {
+            let metadata = serde_json::to_string_pretty(&save::metadata(self)?)?;
+            std::fs::write(directory.join(format!("{stem}.json")), metadata)?;
+        }
+
+        Ok(path)
+    }
+
+    /// Save the article as self-contained folder `<directory>/<name>/` and return the path of
+    /// its `index.html`. Images are stored in `assets/` if `resolver` provides their content,
+    /// all others keep their absolute remote URL. The metadata is written to `metadata.json`.
+    pub fn save_bundle<R>(
+        &self,
+        directory: &Path,
+        options: &SaveOptions,
+        resolver: R,
+    ) -> Result<PathBuf, SaveError>
+    where
+        R: Fn(&Url) -> Option<Resource>,
+    {
+        let html = self.html.as_deref().ok_or(SaveError::NoContent)?;
+        std::fs::create_dir_all(directory)?;
+
+        let stem = save::available_stem(
+            directory,
+            &options.file_stem(self),
+            SAVE_TARGETS,
+            options.collision,
+        )?;
+        let bundle = directory.join(stem);
+        let assets = bundle.join("assets");
+        std::fs::create_dir_all(&bundle)?;
+
+        let mut asset_count = 0;
+        let mut write_error = None;
+        let content = Resource::replace_image_sources(html, &self.url, |url| {
+            let resource = resolver(url).filter(Resource::is_image)?;
+            asset_count += 1;
+            let file_name = format!("image-{asset_count}.{}", resource.extension());
+
+            let result = std::fs::create_dir_all(&assets)
+                .and_then(|()| std::fs::write(assets.join(&file_name), &resource.data));
+            match result {
+                Ok(()) => Some(format!("assets/{file_name}")),
+                Err(error) => {
+                    write_error.get_or_insert(error);
+                    None
+                }
             }
+        })
+        .map_err(|_| SaveError::Xml)?;
+
+        if let Some(error) = write_error {
+            return Err(error.into());
+        }
+
+        let path = bundle.join("index.html");
+        std::fs::write(&path, StandaloneHtml::document(self, &content, None))?;
+
+        #[cfg(feature = "serde")]
+        if options.metadata {
+            let metadata = serde_json::to_string_pretty(&save::metadata(self)?)?;
+            std::fs::write(bundle.join("metadata.json"), metadata)?;
         }
 
-        Err(Error::new(
-            ErrorKind::NotFound,
-            "Article does not contain HTML",
-        ))
+        Ok(path)
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::Article;
+    use crate::author::Author;
+    use chrono::{TimeZone, Utc};
+    use url::Url;
+
+    #[test]
+    fn serde_round_trip() {
+        let mut article = Article::new(Url::parse("https://example.com/a").unwrap());
+        article.title = Some("Title".into());
+        article.authors = vec![Author::new("Jane Doe")];
+        article.date = Some(Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap());
+
+        let json = serde_json::to_value(&article).unwrap();
+        assert_eq!(json["url"], "https://example.com/a");
+        assert_eq!(json["date"], "2024-05-01T12:00:00Z");
+        assert_eq!(json["authors"][0]["name"], "Jane Doe");
+        assert!(json["statistics"].is_null());
+
+        let article: Article = serde_json::from_str(
+            r#"{"url": "https://example.com/b", "date_modified": "2024-05-02T08:30:00+02:00"}"#,
+        )
+        .unwrap();
+        assert_eq!(article.url.as_str(), "https://example.com/b");
+        assert_eq!(
+            article.date_modified,
+            Some(Utc.with_ymd_and_hms(2024, 5, 2, 6, 30, 0).unwrap())
+        );
+        assert!(article.authors.is_empty());
     }
 }
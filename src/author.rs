@@ -0,0 +1,187 @@
+use crate::{constants, util::Util};
+use libxml::tree::Node;
+
+/// An author of an article
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Author {
+    pub name: String,
+    /// Profile page of the author
+    pub url: Option<String>,
+    /// Organization the author works for or is affiliated with
+    pub organization: Option<String>,
+}
+
+impl Author {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: Self::normalize_name(name),
+            url: None,
+            organization: None,
+        }
+    }
+
+    /// Split a byline like `By Jane Doe and John Roe` into separate authors.
+    /// Parts that look like dates or are too long to be a name are dropped.
+    pub fn from_byline(byline: &str) -> Vec<Self> {
+        let byline = match escaper::decode_html(byline) {
+            Ok(decoded) => decoded,
+            Err(_error) => byline.into(),
+        };
+        let byline = constants::NORMALIZE.replace_all(byline.trim(), " ");
+        let byline = constants::AUTHOR_PREFIX.replace(&byline, "");
+
+        let mut authors = Vec::new();
+        for name in constants::AUTHOR_SEPARATOR
+            .split(&byline)
+            .flat_map(Self::split_commas)
+        {
+            let name = name.trim();
+            if name.is_empty()
+                || name.chars().count() > constants::AUTHOR_MAX_LENGTH
+                || name.chars().any(|c| c.is_ascii_digit())
+            {
+                continue;
+            }
+            Self::merge(&mut authors, Self::new(name));
+        }
+        authors
+    }
+
+    // `Jane Doe, John Roe` lists two authors but `Doe, Jane` is a single one:
+    // only split if every part has a first and a last name
+    fn split_commas(text: &str) -> Vec<&str> {
+        let parts = text
+            .split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>();
+
+        if parts
+            .iter()
+            .all(|part| part.split_whitespace().count() >= 2)
+        {
+            parts
+        } else {
+            vec![text]
+        }
+    }
+
+    /// Read an author from an element: the text of `itemprop="name"` or the whole element
+    /// as name, `href` or `itemprop="url"` as URL. The text may contain several authors.
+    pub(crate) fn from_node(node: &Node) -> Vec<Self> {
+        let name_node = Util::get_elements_by_tag_name(node, "*")
+            .into_iter()
+            .find(|child| child.get_attribute("itemprop").as_deref() == Some("name"));
+        let name = match &name_node {
+            Some(name_node) => name_node
+                .get_attribute("content")
+                .unwrap_or_else(|| name_node.get_content()),
+            None => node
+                .get_attribute("content")
+                .unwrap_or_else(|| node.get_content()),
+        };
+
+        let url = node.get_attribute("href").or_else(|| {
+            Util::get_elements_by_tag_name(node, "*")
+                .into_iter()
+                .filter(|child| child.get_attribute("itemprop").as_deref() == Some("url"))
+                .find_map(|child| {
+                    child
+                        .get_attribute("href")
+                        .or_else(|| child.get_attribute("content"))
+                })
+        });
+
+        let mut authors = Self::from_byline(&name);
+        if let [author] = authors.as_mut_slice() {
+            author.url = url.map(|url| url.trim().to_string());
+        }
+        authors
+    }
+
+    /// Add `author` to `authors` unless an author with the same name is already present.
+    /// Missing URL and organization of the present author are completed.
+    pub fn merge(authors: &mut Vec<Author>, author: Author) {
+        if author.name.is_empty() {
+            return;
+        }
+
+        let key = author.name.to_lowercase();
+        match authors
+            .iter_mut()
+            .find(|existing| existing.name.to_lowercase() == key)
+        {
+            Some(existing) => {
+                if existing.url.is_none() {
+                    existing.url = author.url;
+                }
+                if existing.organization.is_none() {
+                    existing.organization = author.organization;
+                }
+            }
+            None => authors.push(author),
+        }
+    }
+
+    fn normalize_name(name: &str) -> String {
+        constants::NORMALIZE
+            .replace_all(name.trim(), " ")
+            .trim_matches(|c: char| c == ',' || c == '|' || c == '-' || c.is_whitespace())
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Author;
+
+    fn names(byline: &str) -> Vec<String> {
+        Author::from_byline(byline)
+            .into_iter()
+            .map(|author| author.name)
+            .collect()
+    }
+
+    #[test]
+    fn split_byline() {
+        assert_eq!(
+            names("By Jane Doe and John Roe"),
+            vec!["Jane Doe", "John Roe"]
+        );
+        assert_eq!(
+            names("by Jane Doe, John Roe, and Max Mustermann"),
+            vec!["Jane Doe", "John Roe", "Max Mustermann"]
+        );
+        assert_eq!(
+            names("Von Erika Mustermann und Max Mustermann"),
+            vec!["Erika Mustermann", "Max Mustermann"]
+        );
+        assert_eq!(names("Jane Doe &amp; jane doe"), vec!["Jane Doe"]);
+        assert_eq!(names("By Jane Doe | March 5, 2024"), vec!["Jane Doe"]);
+        assert_eq!(names("Doe, Jane"), vec!["Doe, Jane"]);
+        assert_eq!(names("Jane Y. Doe"), vec!["Jane Y. Doe"]);
+        assert_eq!(
+            names("Anna E. Smith, John Roe"),
+            vec!["Anna E. Smith", "John Roe"]
+        );
+    }
+
+    #[test]
+    fn merge_completes_fields() {
+        let mut authors = vec![Author::new("Jane Doe")];
+        Author::merge(
+            &mut authors,
+            Author {
+                name: "jane doe".into(),
+                url: Some("https://example.com/jane".into()),
+                organization: Some("Example News".into()),
+            },
+        );
+
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].name, "Jane Doe");
+        assert_eq!(authors[0].url.as_deref(), Some("https://example.com/jane"));
+        assert_eq!(authors[0].organization.as_deref(), Some("Example News"));
+    }
+}
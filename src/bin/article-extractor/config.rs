@@ -0,0 +1,156 @@
+use article_extractor::{ConfigSeverity, FtrConfigEntry, FullTextParser};
+use clap::Subcommand;
+use std::path::{Path, PathBuf};
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Report problems in every site config (`.txt`) of a directory
+    Lint {
+        /// Directory with site configs
+        dir: PathBuf,
+    },
+    /// Run site configs against stored pages and report XPaths that match nothing
+    Test {
+        /// Directory with site configs
+        dir: PathBuf,
+
+        /// Directory with the stored pages: `<config name>.html` or `<config name>/*.html`,
+        /// e.g. `example.com.html` for `example.com.txt`
+        #[arg(long)]
+        snapshots: PathBuf,
+    },
+}
+
+pub fn run(command: &ConfigCommand, quiet: bool) -> Result<(), String> {
+    match command {
+        ConfigCommand::Lint { dir } => lint(dir, quiet),
+        ConfigCommand::Test { dir, snapshots } => test(dir, snapshots, quiet),
+    }
+}
+
+fn lint(dir: &Path, quiet: bool) -> Result<(), String> {
+    let configs = files(dir, &["txt"])?;
+    let mut errors = 0;
+
+    for config in &configs {
+        let diagnostics = FtrConfigEntry::lint_path(config)
+            .map_err(|error| format!("failed to read '{}': {error}", config.display()))?;
+
+        for diagnostic in diagnostics {
+            if diagnostic.severity == ConfigSeverity::Error {
+                errors += 1;
+            } else if quiet {
+                continue;
+            }
+            println!("{}:{diagnostic}", config.display());
+        }
+    }
+
+    if errors > 0 {
+        Err(format!("{errors} errors in {} configs", configs.len()))
+    } else {
+        Ok(())
+    }
+}
+
+fn test(dir: &Path, snapshots: &Path, quiet: bool) -> Result<(), String> {
+    let parser = FullTextParser::new(None);
+    let mut tested = 0;
+    let mut failed = 0;
+
+    for config_path in files(dir, &["txt"])? {
+        let Some(name) = config_path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        let pages = snapshot_pages(snapshots, name)?;
+        if pages.is_empty() {
+            continue;
+        }
+
+        let config = FtrConfigEntry::parse_path(&config_path)
+            .map_err(|error| format!("failed to read '{}': {error}", config_path.display()))?;
+
+        for page in pages {
+            tested += 1;
+            let data = std::fs::read(&page)
+                .map_err(|error| format!("failed to read '{}': {error}", page.display()))?;
+            let html = FullTextParser::decode_html(&data, None);
+
+            let matches = match parser.match_config_xpaths(&config, &html) {
+                Ok(matches) => matches,
+                Err(error) => {
+                    failed += 1;
+                    println!("{}: {}: error: {error}", name, page.display());
+                    continue;
+                }
+            };
+
+            // like the parser: the config is only used if one of the body XPaths matches
+            let body_matched = matches.iter().any(|m| m.directive == "body" && m.count > 0);
+            if !body_matched && !config.xpath_body.is_empty() {
+                failed += 1;
+            }
+
+            for m in matches
+                .iter()
+                .filter(|m| m.count == 0 && matches!(m.directive.as_str(), "title" | "body"))
+            {
+                println!(
+                    "{}: {}: {} matched nothing: {}",
+                    name,
+                    page.display(),
+                    m.directive,
+                    m.xpath
+                );
+            }
+
+            if !quiet && body_matched {
+                println!("{}: {}: ok", name, page.display());
+            }
+        }
+    }
+
+    if failed > 0 {
+        Err(format!("{failed} of {tested} pages failed"))
+    } else if tested == 0 {
+        Err(format!("no snapshots found in '{}'", snapshots.display()))
+    } else {
+        Ok(())
+    }
+}
+
+/// `<snapshots>/<name>.html` and the HTML files of `<snapshots>/<name>/`
+fn snapshot_pages(snapshots: &Path, name: &str) -> Result<Vec<PathBuf>, String> {
+    let mut pages = Vec::new();
+
+    let page = snapshots.join(format!("{name}.html"));
+    if page.is_file() {
+        pages.push(page);
+    }
+
+    let dir = snapshots.join(name);
+    if dir.is_dir() {
+        pages.extend(files(&dir, &["html", "htm"])?);
+    }
+
+    Ok(pages)
+}
+
+/// Files of `dir` with one of the extensions, sorted by name
+pub fn files(dir: &Path, extensions: &[&str]) -> Result<Vec<PathBuf>, String> {
+    let mut files = std::fs::read_dir(dir)
+        .map_err(|error| format!("failed to read '{}': {error}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .map(|extension| extensions.contains(&extension.to_ascii_lowercase().as_str()))
+                    .unwrap_or(false)
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,259 @@
+mod config;
+
+use article_extractor::{
+    Article, FtrConfigEntry, FullTextParser, Readability, Statistics, TextOptions,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use config::ConfigCommand;
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use url::Url;
+
+/// Extract the article of HTML pages
+#[derive(Parser)]
+#[command(
+    name = "article-extractor",
+    version,
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// HTML file or a directory of HTML files (batch mode). Reads stdin if omitted or `-`.
+    input: Option<PathBuf>,
+
+    /// URL of the page: selects the site config and completes relative links
+    #[arg(long)]
+    url: Option<Url>,
+
+    /// Directory with site configs overriding the embedded ones
+    #[arg(long)]
+    config_dir: Option<PathBuf>,
+
+    /// Ignore site configs and only use the Readability algorithm
+    #[arg(long)]
+    readability_only: bool,
+
+    #[arg(long, value_enum, default_value_t = Format::Html)]
+    format: Format,
+
+    /// Output file. Required in batch mode: directory the results are written to.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Don't print the metadata to stderr
+    #[arg(short, long, global = true)]
+    quiet: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check site configs
+    #[command(subcommand)]
+    Config(ConfigCommand),
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Html,
+    Text,
+    Markdown,
+    /// Metadata and HTML content
+    Json,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Text => "txt",
+            Self::Markdown => "md",
+            Self::Json => "json",
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    env_logger::init();
+    let cli = Cli::parse();
+
+    let result = match (&cli.command, cli.input.as_deref()) {
+        (Some(Command::Config(command)), _) => config::run(command, cli.quiet),
+        (None, Some(input)) if input.is_dir() => batch(&cli, input),
+        (None, input) => single(&cli, input),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn single(cli: &Cli, input: Option<&Path>) -> Result<(), String> {
+    let html = match input {
+        Some(path) if path != Path::new("-") => read_html(path)?,
+        _ => {
+            let mut data = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut data)
+                .map_err(|error| format!("failed to read stdin: {error}"))?;
+            FullTextParser::decode_html(&data, None)
+        }
+    };
+
+    let parser = (!cli.readability_only).then(|| FullTextParser::new(cli.config_dir.as_deref()));
+    let article = extract(cli, parser.as_ref(), html)?;
+
+    if !cli.quiet {
+        print_metadata(&article);
+    }
+
+    let output = render(&article, cli.format)?;
+    match cli.output.as_deref() {
+        Some(path) => std::fs::write(path, output)
+            .map_err(|error| format!("failed to write '{}': {error}", path.display())),
+        None => std::io::stdout()
+            .write_all(output.as_bytes())
+            .map_err(|error| format!("failed to write output: {error}")),
+    }
+}
+
+fn batch(cli: &Cli, directory: &Path) -> Result<(), String> {
+    let output = cli
+        .output
+        .as_deref()
+        .ok_or("batch mode requires an output directory (--output)")?;
+    std::fs::create_dir_all(output)
+        .map_err(|error| format!("failed to create '{}': {error}", output.display()))?;
+
+    let files = config::files(directory, &["html", "htm", "xhtml"])?;
+
+    let parser = (!cli.readability_only).then(|| FullTextParser::new(cli.config_dir.as_deref()));
+    let mut failed = 0;
+    let mut targets = HashSet::new();
+
+    for file in &files {
+        let stem = file.file_stem().unwrap_or_default();
+        let target = output.join(format!(
+            "{}.{}",
+            stem.to_string_lossy(),
+            cli.format.extension()
+        ));
+
+        // `a.html` and `a.htm` would both be written to `a.<ext>`
+        let result = if targets.insert(target.clone()) {
+            read_html(file)
+                .and_then(|html| extract(cli, parser.as_ref(), html))
+                .and_then(|article| {
+                    std::fs::write(&target, render(&article, cli.format)?).map_err(|error| {
+                        format!("failed to write '{}': {error}", target.display())
+                    })?;
+                    Ok(article)
+                })
+        } else {
+            Err(format!(
+                "'{}' is already written by another input with the same name",
+                target.display()
+            ))
+        };
+
+        match result {
+            Ok(article) if !cli.quiet => eprintln!(
+                "{}: {}",
+                file.display(),
+                article.title.as_deref().unwrap_or("(no title)")
+            ),
+            Ok(_) => {}
+            Err(error) => {
+                failed += 1;
+                eprintln!("{}: error: {error}", file.display());
+            }
+        }
+    }
+
+    if failed > 0 {
+        Err(format!("{failed} of {} files failed", files.len()))
+    } else {
+        Ok(())
+    }
+}
+
+fn read_html(path: &Path) -> Result<String, String> {
+    let data = std::fs::read(path)
+        .map_err(|error| format!("failed to read '{}': {error}", path.display()))?;
+    Ok(FullTextParser::decode_html(&data, None))
+}
+
+fn extract(cli: &Cli, parser: Option<&FullTextParser>, html: String) -> Result<Article, String> {
+    let result = match parser {
+        Some(parser) => parser.parse_offline(vec![html], None::<&FtrConfigEntry>, cli.url.clone()),
+        None => Readability::extract_article(&html, cli.url.clone()),
+    };
+    result.map_err(|error| format!("extraction failed: {error}"))
+}
+
+fn render(article: &Article, format: Format) -> Result<String, String> {
+    let output = match format {
+        Format::Html => article.html.clone(),
+        Format::Text => article.to_text(&TextOptions::default()),
+        Format::Markdown => article.to_markdown(),
+        Format::Json => {
+            return serde_json::to_string_pretty(article)
+                .map(|json| json + "\n")
+                .map_err(|error| format!("failed to serialize article: {error}"))
+        }
+    };
+
+    output.ok_or_else(|| "no content extracted".into())
+}
+
+fn print_metadata(article: &Article) {
+    let mut lines = Vec::new();
+    let mut line = |name: &str, value: Option<String>| {
+        if let Some(value) = value {
+            lines.push(format!("{name}: {value}"));
+        }
+    };
+
+    line("Title", article.title.clone());
+    line("Author", article.author.clone());
+    line("Published", article.date.map(|date| date.to_rfc3339()));
+    line(
+        "Modified",
+        article.date_modified.map(|date| date.to_rfc3339()),
+    );
+    line("Site", article.site_name.clone());
+    line("Language", article.language.clone());
+    line(
+        "URL",
+        Some(
+            article
+                .canonical_url
+                .as_ref()
+                .unwrap_or(&article.url)
+                .to_string(),
+        ),
+    );
+    line(
+        "Tags",
+        (!article.tags.is_empty()).then(|| article.tags.join(", ")),
+    );
+    line(
+        "Words",
+        article.statistics.map(|statistics| {
+            let minutes = statistics
+                .reading_time(Statistics::DEFAULT_WORDS_PER_MINUTE)
+                .as_secs()
+                .div_ceil(60);
+            format!("{} ({minutes} min read)", statistics.word_count)
+        }),
+    );
+
+    eprintln!("{}\n", lines.join("\n"));
+}
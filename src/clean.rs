@@ -1,6 +1,6 @@
 use crate::full_text_parser::error::FullTextParserError;
 use crate::util::Util;
-use crate::{FtrConfigEntry, FullTextParser};
+use crate::{FtrConfigEntry, FullTextParser, ReadabilityOptions};
 use url::Url;
 
 pub struct CleanedHtml {
@@ -44,10 +44,10 @@ pub fn clean_html_fragment(
     let thumbnail = FullTextParser::check_for_thumbnail(&xpath_ctx);
     FullTextParser::prep_content(&xpath_ctx, None, &empty_config, base_url, &document, None);
     if let Some(mut root) = document.get_root_element() {
-        FullTextParser::post_process_page(&mut root)?;
+        FullTextParser::post_process_page(&mut root, &ReadabilityOptions::default())?;
     }
     FullTextParser::prevent_self_closing_tags(&xpath_ctx)?;
-    FullTextParser::post_process_document(&document)?;
+    FullTextParser::post_process_document(&document, &ReadabilityOptions::default())?;
 
     let content_node = if let Some(root) = document.get_root_element() {
         if root.get_name() == "body" {
@@ -4,6 +4,14 @@ use once_cell::sync::Lazy;
 use regex::{Regex, RegexBuilder};
 
 pub const DEFAULT_CHAR_THRESHOLD: usize = 500;
+// Mozilla's list of commas used to score paragraphs & to spare elements while cleaning, plus the
+// ideographic comma `、` (U+3001) and its halfwidth form (U+FF64). Chinese & Japanese separate
+// enumerations with them instead of `，`, so without them CJK text has fewer commas than latin.
+pub const COMMAS: &[char] = &[
+    ',', '\u{060C}', '\u{FE50}', '\u{FE10}', '\u{FE11}', '\u{2E41}', '\u{2E34}', '\u{2E32}',
+    '\u{FF0C}', '\u{3001}', '\u{FF64}',
+];
+pub const DEFAULT_WORDS_PER_MINUTE: usize = 230;
 pub static IS_IMAGE: Lazy<Regex> = Lazy::new(|| {
     RegexBuilder::new(r#"\.(jpg|jpeg|png|webp)"#)
         .case_insensitive(true)
@@ -92,9 +100,235 @@ pub static BASE64_DATA_URL: Lazy<Regex> = Lazy::new(|| {
         .build()
         .expect("BASE64_DATA_URL regex")
 });
+pub const FOOTNOTE_MARKER_XPATH: &str = "//sup//a[starts-with(@href, '#')] | //a[@role='doc-noteref' or contains(@class, 'footnote-ref')][starts-with(@href, '#')]";
+pub static FOOTNOTE: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(r#"(?:^|[\s_-])(?:fn|ftn|footnotes?|endnotes?|notes?)(?:[\s_\d-]|$)"#)
+        .case_insensitive(true)
+        .build()
+        .expect("FOOTNOTE regex")
+});
+pub const FOOTNOTE_MAX_LENGTH: usize = 2000;
+pub const FOOTNOTE_BACKLINK_TEXT: &[&str] = &["↩", "↩\u{fe0e}", "↑", "^"];
+pub static JSON_LD_ARTICLE_TYPES: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^(Article|AdvancedArticle|AnalysisNewsArticle|AskPublicNewsArticle|BackgroundNewsArticle|BlogPosting|DiscussionForumPosting|LiveBlogPosting|MedicalScholarlyArticle|NewsArticle|OpinionNewsArticle|Report|ReportageNewsArticle|ReviewNewsArticle|SatiricalArticle|ScholarlyArticle|SocialMediaPosting|TechArticle)$"#).expect("JSON_LD_ARTICLE_TYPES regex")
+});
+pub static JSON_LD_CDATA: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*<!\[CDATA\[|\]\]>\s*$"#).expect("JSON_LD_CDATA regex"));
+pub static DATE_YMD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:^|\D)(\d{4})[-/.](\d{1,2})[-/.](\d{1,2})(?:\D|$)"#).expect("DATE_YMD regex")
+});
+pub static DATE_DMY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:^|\D)(\d{1,2})\.\s?(\d{1,2})\.\s?(\d{4}|\d{2})(?:\D|$)"#)
+        .expect("DATE_DMY regex")
+});
+pub static DATE_MDY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:^|\D)(\d{1,2})/(\d{1,2})/(\d{4})(?:\D|$)"#).expect("DATE_MDY regex")
+});
+pub static DATE_TIME: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(
+        r#"(?:^|\D)(\d{1,2}):(\d{2})(?::(\d{2}))?(?:[.,]\d+)?(?:\s*([ap])\.?\s?m\b\.?)?"#,
+    )
+    .case_insensitive(true)
+    .build()
+    .expect("DATE_TIME regex")
+});
+pub static DATE_HOUR: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(r#"(?:^|\D)(\d{1,2})\s*([ap])\.?\s?m\b\.?"#)
+        .case_insensitive(true)
+        .build()
+        .expect("DATE_HOUR regex")
+});
+pub static DATE_OFFSET: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"([+-])(\d{2}):?(\d{2})"#).expect("DATE_OFFSET regex"));
+pub static DATE_ZONE: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(r#"\b(utc|gmt|z|est|edt|cst|cdt|mst|mdt|pst|pdt|cet|cest|bst|eet|eest)\b"#)
+        .case_insensitive(true)
+        .build()
+        .expect("DATE_ZONE regex")
+});
+pub static DATE_RELATIVE: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(r#"(?:(\d+)|\ban?\b|\bone\b)\s+(second|sec|minute|min|hour|hr|day|week|month|year)s?\s+ago\b"#)
+        .case_insensitive(true)
+        .build()
+        .expect("DATE_RELATIVE regex")
+});
+pub static DATE_RELATIVE_DE: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(
+        r#"\bvor\s+(?:(\d+)|einer?|einem)\s+(sekunde|minute|stunde|tag|woche|monat|jahr)"#,
+    )
+    .case_insensitive(true)
+    .build()
+    .expect("DATE_RELATIVE_DE regex")
+});
+pub static DATE_UPDATED: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(
+        r#"updat|modifi|revis|aktualisiert|geändert|mis à jour|actualiz|aggiornat|bijgewerkt"#,
+    )
+    .case_insensitive(true)
+    .build()
+    .expect("DATE_UPDATED regex")
+});
+pub static AUTHOR_PREFIX: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(
+        r#"^(?:(?:written|posted|published|reported|text)\s+)?(?:by|von|par|por)\b\s*:?\s*"#,
+    )
+    .case_insensitive(true)
+    .build()
+    .expect("AUTHOR_PREFIX regex")
+});
+pub static AUTHOR_SEPARATOR: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(r#"\s*(?:[;&|/•·]|\band\b|\bund\b)\s*"#)
+        .case_insensitive(true)
+        .build()
+        .expect("AUTHOR_SEPARATOR regex")
+});
+pub const AUTHOR_MAX_LENGTH: usize = 100;
+pub const EXCERPT_MIN_LENGTH: usize = 80;
+pub const TAG_MAX_LENGTH: usize = 100;
+pub static PRINT_LINK: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(r#"^(print|printer|print version|printable version|drucken|druckversion|imprimer|imprimir|stampa)$|print-?(link|version|button)|printable"#)
+        .case_insensitive(true)
+        .build()
+        .expect("PRINT_LINK regex")
+});
+pub static LANGUAGE_TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^[a-zA-Z]{2,3}(-[a-zA-Z0-9]{2,8})*$"#).expect("LANGUAGE_TAG regex"));
+pub const RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "ha", "he", "iw", "khw", "ks", "ku", "ps", "sd", "ur", "yi",
+];
+pub const DATE_ZONE_OFFSETS: &[(&str, i32)] = &[
+    ("utc", 0),
+    ("gmt", 0),
+    ("z", 0),
+    ("est", -5),
+    ("edt", -4),
+    ("cst", -6),
+    ("cdt", -5),
+    ("mst", -7),
+    ("mdt", -6),
+    ("pst", -8),
+    ("pdt", -7),
+    ("cet", 1),
+    ("cest", 2),
+    ("bst", 1),
+    ("eet", 2),
+    ("eest", 3),
+];
+// abbreviations in `MONTH_NAMES` that are also common words:
+// only taken as month if a day or year is right next to them
+pub const AMBIGUOUS_MONTH_NAMES: &[&str] =
+    &["out", "set", "mar", "may", "gen", "mag", "dic", "ene"];
+// english, german, french, spanish, italian, dutch & portuguese month names and abbreviations
+pub const MONTH_NAMES: &[(&str, u32)] = &[
+    ("january", 1),
+    ("jan", 1),
+    ("januar", 1),
+    ("jänner", 1),
+    ("janvier", 1),
+    ("janv", 1),
+    ("enero", 1),
+    ("ene", 1),
+    ("gennaio", 1),
+    ("gen", 1),
+    ("januari", 1),
+    ("janeiro", 1),
+    ("february", 2),
+    ("feb", 2),
+    ("februar", 2),
+    ("février", 2),
+    ("fevrier", 2),
+    ("févr", 2),
+    ("febrero", 2),
+    ("febbraio", 2),
+    ("februari", 2),
+    ("fevereiro", 2),
+    ("fev", 2),
+    ("march", 3),
+    ("mar", 3),
+    ("märz", 3),
+    ("marz", 3),
+    ("mär", 3),
+    ("mars", 3),
+    ("marzo", 3),
+    ("maart", 3),
+    ("março", 3),
+    ("marco", 3),
+    ("april", 4),
+    ("apr", 4),
+    ("avril", 4),
+    ("avr", 4),
+    ("abril", 4),
+    ("abr", 4),
+    ("aprile", 4),
+    ("may", 5),
+    ("mai", 5),
+    ("mayo", 5),
+    ("maggio", 5),
+    ("mag", 5),
+    ("mei", 5),
+    ("maio", 5),
+    ("june", 6),
+    ("jun", 6),
+    ("juni", 6),
+    ("juin", 6),
+    ("junio", 6),
+    ("giugno", 6),
+    ("giu", 6),
+    ("junho", 6),
+    ("july", 7),
+    ("jul", 7),
+    ("juli", 7),
+    ("juillet", 7),
+    ("juil", 7),
+    ("julio", 7),
+    ("luglio", 7),
+    ("lug", 7),
+    ("julho", 7),
+    ("august", 8),
+    ("aug", 8),
+    ("août", 8),
+    ("aout", 8),
+    ("agosto", 8),
+    ("september", 9),
+    ("sep", 9),
+    ("sept", 9),
+    ("septembre", 9),
+    ("septiembre", 9),
+    ("setiembre", 9),
+    ("settembre", 9),
+    ("set", 9),
+    ("setembro", 9),
+    ("october", 10),
+    ("oct", 10),
+    ("oktober", 10),
+    ("okt", 10),
+    ("octobre", 10),
+    ("octubre", 10),
+    ("ottobre", 10),
+    ("ott", 10),
+    ("outubro", 10),
+    ("out", 10),
+    ("november", 11),
+    ("nov", 11),
+    ("novembre", 11),
+    ("noviembre", 11),
+    ("novembro", 11),
+    ("december", 12),
+    ("dec", 12),
+    ("dezember", 12),
+    ("dez", 12),
+    ("décembre", 12),
+    ("decembre", 12),
+    ("déc", 12),
+    ("diciembre", 12),
+    ("dic", 12),
+    ("dicembre", 12),
+    ("dezembro", 12),
+];
 pub const SCORE_ATTR: &str = "content_score";
 pub const DATA_TABLE_ATTR: &str = "is_data_table";
 pub const MINIMUM_TOPCANDIDATES: usize = 3;
+pub const DEFAULT_N_TOP_CANDIDATES: usize = 5;
 pub const UNLIKELY_ROLES: &[&str] = &[
     "menu",
     "menubar",
@@ -232,3 +466,51 @@ pub static JPG_REGEX: Lazy<Regex> = Lazy::new(|| {
         .build()
         .expect("JPG_REGEX")
 });
+
+pub static BLOCK_ELEMS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    HashSet::from([
+        "ADDRESS",
+        "ARTICLE",
+        "ASIDE",
+        "CENTER",
+        "DD",
+        "DETAILS",
+        "DIV",
+        "DL",
+        "DT",
+        "FIELDSET",
+        "FIGCAPTION",
+        "FIGURE",
+        "FOOTER",
+        "FORM",
+        "HEADER",
+        "IMAGEOBJECT",
+        "MAIN",
+        "NAV",
+        "P",
+        "SECTION",
+        "SUMMARY",
+    ])
+});
+
+pub static SKIP_OUTPUT_ELEMS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    HashSet::from([
+        "HEAD", "NOSCRIPT", "SCRIPT", "STYLE", "TEMPLATE", "IFRAME", "BUTTON", "INPUT", "SELECT",
+    ])
+});
+pub const TEXT_MIN_LINE_WIDTH: usize = 20;
+
+pub static CHARSET: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(r#"charset\s*=\s*["']?\s*([\w:.-]+)"#)
+        .case_insensitive(true)
+        .build()
+        .expect("CHARSET regex")
+});
+pub static META_CHARSET: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(r#"<meta[^>]+charset\s*=\s*["']?\s*([\w:.-]+)"#)
+        .case_insensitive(true)
+        .build()
+        .expect("META_CHARSET regex")
+});
+// only the beginning of a document is searched for a `<meta charset>`
+pub const CHARSET_SNIFF_LENGTH: usize = 4096;
@@ -0,0 +1,392 @@
+use crate::constants;
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Months, NaiveDate, NaiveTime, TimeZone, Utc,
+};
+
+/// Lenient parser for the date strings found in articles
+///
+/// Supports RFC 3339, RFC 2822, unix timestamps, numeric dates (`2024-03-05`,
+/// `05.03.2024`, `03/05/2024`), dates with english, german, french, spanish, italian,
+/// dutch or portuguese month names (`March 5, 2024`, `5. März 2024`) and relative
+/// dates (`3 hours ago`, `yesterday`, `vor 2 Tagen`). An optional time, utc offset
+/// or common time zone abbreviation is respected, otherwise UTC is assumed.
+pub struct DateParser;
+
+impl DateParser {
+    /// Parse a date string. Relative dates are resolved against the current time.
+    pub fn parse(date_string: &str) -> Option<DateTime<Utc>> {
+        Self::parse_with_reference(date_string, Utc::now())
+    }
+
+    /// Parse a date string. Relative dates and dates without a year are resolved
+    /// against `reference`.
+    pub fn parse_with_reference(
+        date_string: &str,
+        reference: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let date_string = date_string.trim();
+        if date_string.is_empty() {
+            return None;
+        }
+
+        if let Ok(date) = DateTime::parse_from_rfc3339(date_string) {
+            return Some(date.with_timezone(&Utc));
+        }
+
+        if let Ok(date) = DateTime::parse_from_rfc2822(date_string) {
+            return Some(date.with_timezone(&Utc));
+        }
+
+        if let Some(date) = Self::parse_timestamp(date_string) {
+            return Some(date);
+        }
+
+        let normalized = date_string.to_lowercase();
+
+        Self::parse_relative(&normalized, reference)
+            .or_else(|| Self::parse_absolute(&normalized, reference))
+    }
+
+    fn parse_timestamp(date_string: &str) -> Option<DateTime<Utc>> {
+        if !date_string.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let timestamp = date_string.parse::<i64>().ok()?;
+        match date_string.len() {
+            10 => DateTime::from_timestamp(timestamp, 0),
+            13 => DateTime::from_timestamp_millis(timestamp),
+            _ => None,
+        }
+    }
+
+    fn parse_relative(date_string: &str, reference: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if let Some(captures) = constants::DATE_RELATIVE.captures(date_string) {
+            let amount = match captures.get(1) {
+                Some(amount) => amount.as_str().parse::<u32>().ok()?,
+                None => 1,
+            };
+            let unit = captures.get(2)?.as_str();
+            return Self::subtract(reference, amount, unit);
+        }
+
+        if let Some(captures) = constants::DATE_RELATIVE_DE.captures(date_string) {
+            let amount = match captures.get(1) {
+                Some(amount) => amount.as_str().parse::<u32>().ok()?,
+                None => 1,
+            };
+            let unit = match captures.get(2)?.as_str() {
+                "sekunde" => "second",
+                "minute" => "minute",
+                "stunde" => "hour",
+                "tag" => "day",
+                "woche" => "week",
+                "monat" => "month",
+                _ => "year",
+            };
+            return Self::subtract(reference, amount, unit);
+        }
+
+        let days_ago = if date_string.starts_with("just now")
+            || date_string.starts_with("gerade eben")
+            || date_string == "now"
+        {
+            return Some(reference);
+        } else if date_string.starts_with("today") || date_string.starts_with("heute") {
+            0
+        } else if date_string.starts_with("yesterday") || date_string.starts_with("gestern") {
+            1
+        } else {
+            return None;
+        };
+
+        let date = (reference - Duration::days(days_ago)).date_naive();
+        match Self::parse_time(date_string) {
+            Some((time, _rest)) => {
+                let offset = Self::parse_offset(date_string).unwrap_or(Self::utc());
+                offset
+                    .from_local_datetime(&date.and_time(time))
+                    .single()
+                    .map(|date| date.with_timezone(&Utc))
+            }
+            None => Some(reference - Duration::days(days_ago)),
+        }
+    }
+
+    fn subtract(reference: DateTime<Utc>, amount: u32, unit: &str) -> Option<DateTime<Utc>> {
+        let amount_i64 = i64::from(amount);
+        match unit {
+            "second" | "sec" => reference.checked_sub_signed(Duration::try_seconds(amount_i64)?),
+            "minute" | "min" => reference.checked_sub_signed(Duration::try_minutes(amount_i64)?),
+            "hour" | "hr" => reference.checked_sub_signed(Duration::try_hours(amount_i64)?),
+            "day" => reference.checked_sub_signed(Duration::try_days(amount_i64)?),
+            "week" => reference.checked_sub_signed(Duration::try_weeks(amount_i64)?),
+            "month" => reference.checked_sub_months(Months::new(amount)),
+            "year" => reference.checked_sub_months(Months::new(amount.checked_mul(12)?)),
+            _ => None,
+        }
+    }
+
+    fn parse_absolute(date_string: &str, reference: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let (date, rest) = Self::parse_numeric_date(date_string)
+            .or_else(|| Self::parse_textual_date(date_string, reference))?;
+
+        let (time, rest) = Self::parse_time(&rest).unwrap_or((NaiveTime::MIN, rest));
+        let offset = Self::parse_offset(&rest).unwrap_or(Self::utc());
+
+        offset
+            .from_local_datetime(&date.and_time(time))
+            .single()
+            .map(|date| date.with_timezone(&Utc))
+    }
+
+    // Returns the date and the remaining string without the date part
+    fn parse_numeric_date(date_string: &str) -> Option<(NaiveDate, String)> {
+        if let Some(captures) = constants::DATE_YMD.captures(date_string) {
+            let date = NaiveDate::from_ymd_opt(
+                captures[1].parse().ok()?,
+                captures[2].parse().ok()?,
+                captures[3].parse().ok()?,
+            )?;
+            return Some((date, Self::remove_match(date_string, &captures)));
+        }
+
+        if let Some(captures) = constants::DATE_DMY.captures(date_string) {
+            let year: i32 = captures[3].parse().ok()?;
+            let year = if year < 100 { 2000 + year } else { year };
+            let date = NaiveDate::from_ymd_opt(
+                year,
+                captures[2].parse().ok()?,
+                captures[1].parse().ok()?,
+            )?;
+            return Some((date, Self::remove_match(date_string, &captures)));
+        }
+
+        if let Some(captures) = constants::DATE_MDY.captures(date_string) {
+            let year: i32 = captures[3].parse().ok()?;
+            let first: u32 = captures[1].parse().ok()?;
+            let second: u32 = captures[2].parse().ok()?;
+
+            // prefer the US order unless the first number can't be a month
+            let date = NaiveDate::from_ymd_opt(year, first, second)
+                .or_else(|| NaiveDate::from_ymd_opt(year, second, first))?;
+            return Some((date, Self::remove_match(date_string, &captures)));
+        }
+
+        None
+    }
+
+    fn parse_textual_date(
+        date_string: &str,
+        reference: DateTime<Utc>,
+    ) -> Option<(NaiveDate, String)> {
+        // times contain numbers that could be confused with the day
+        let without_time = match Self::parse_time(date_string) {
+            Some((_time, rest)) => rest,
+            None => date_string.to_string(),
+        };
+
+        let mut month = None;
+        let mut day = None;
+        let mut year = None;
+
+        let words = without_time
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>();
+
+        for (index, word) in words.iter().enumerate() {
+            if month.is_none() {
+                if let Some((name, number)) =
+                    constants::MONTH_NAMES.iter().find(|(name, _)| name == word)
+                {
+                    let next_to_number = (index > 0 && Self::number(words[index - 1]).is_some())
+                        || words
+                            .get(index + 1)
+                            .and_then(|word| Self::number(word))
+                            .is_some();
+                    if next_to_number || !constants::AMBIGUOUS_MONTH_NAMES.contains(name) {
+                        month = Some(*number);
+                        continue;
+                    }
+                }
+            }
+
+            let Some(digits) = Self::number(word) else {
+                continue;
+            };
+
+            if digits.len() == 4 && year.is_none() {
+                year = digits.parse::<i32>().ok();
+            } else if digits.len() <= 2 && day.is_none() {
+                day = digits.parse::<u32>().ok();
+            }
+        }
+
+        let month = month?;
+        let day = day?;
+        let date = match year {
+            Some(year) => NaiveDate::from_ymd_opt(year, month, day)?,
+            None => {
+                // no year given: assume the most recent occurrence
+                let reference_date = reference.date_naive();
+                let date = NaiveDate::from_ymd_opt(reference_date.year(), month, day)?;
+                if date > reference_date {
+                    NaiveDate::from_ymd_opt(reference_date.year() - 1, month, day)?
+                } else {
+                    date
+                }
+            }
+        };
+
+        let rest = match constants::DATE_ZONE.find(&without_time) {
+            Some(zone) => zone.as_str().to_string(),
+            None => constants::DATE_OFFSET
+                .find(&without_time)
+                .map(|offset| offset.as_str().to_string())
+                .unwrap_or_default(),
+        };
+
+        // keep the time for `parse_absolute`
+        let time = Self::parse_time(date_string)
+            .map(|(time, _rest)| time.format("%H:%M:%S ").to_string())
+            .unwrap_or_default();
+
+        Some((date, format!("{time}{rest}")))
+    }
+
+    // digits of a day or year like `5`, `5th`, `1er` or `2024`
+    fn number(word: &str) -> Option<&str> {
+        let digits = word.trim_end_matches(|c: char| c.is_alphabetic());
+        let suffix = &word[digits.len()..];
+        (!digits.is_empty()
+            && digits.chars().all(|c| c.is_ascii_digit())
+            && matches!(suffix, "" | "st" | "nd" | "rd" | "th" | "er" | "e"))
+        .then_some(digits)
+    }
+
+    // Returns the time and the remaining string without the time part
+    fn parse_time(date_string: &str) -> Option<(NaiveTime, String)> {
+        let (captures, hour, minute, second, meridiem) =
+            if let Some(captures) = constants::DATE_TIME.captures(date_string) {
+                let hour: u32 = captures[1].parse().ok()?;
+                let minute: u32 = captures[2].parse().ok()?;
+                let second: u32 = captures
+                    .get(3)
+                    .and_then(|second| second.as_str().parse().ok())
+                    .unwrap_or(0);
+                let meridiem = captures.get(4).map(|m| m.as_str().to_string());
+                (captures, hour, minute, second, meridiem)
+            } else if let Some(captures) = constants::DATE_HOUR.captures(date_string) {
+                let hour: u32 = captures[1].parse().ok()?;
+                let meridiem = captures.get(2).map(|m| m.as_str().to_string());
+                (captures, hour, 0, 0, meridiem)
+            } else {
+                return None;
+            };
+
+        let hour = match meridiem.as_deref() {
+            Some("p") if hour < 12 => hour + 12,
+            Some("a") if hour == 12 => 0,
+            _ => hour,
+        };
+
+        let time = NaiveTime::from_hms_opt(hour, minute, second)?;
+        Some((time, Self::remove_match(date_string, &captures)))
+    }
+
+    fn parse_offset(date_string: &str) -> Option<FixedOffset> {
+        if let Some(captures) = constants::DATE_OFFSET.captures(date_string) {
+            let hours: i32 = captures[2].parse().ok()?;
+            let minutes: i32 = captures[3].parse().ok()?;
+            let seconds = hours * 3600 + minutes * 60;
+            return if &captures[1] == "-" {
+                FixedOffset::west_opt(seconds)
+            } else {
+                FixedOffset::east_opt(seconds)
+            };
+        }
+
+        let zone = constants::DATE_ZONE.find(date_string)?;
+        constants::DATE_ZONE_OFFSETS
+            .iter()
+            .find(|(name, _)| *name == zone.as_str())
+            .and_then(|(_name, hours)| FixedOffset::east_opt(hours * 3600))
+    }
+
+    fn remove_match(date_string: &str, captures: &regex::Captures) -> String {
+        let full_match = captures.get(0).expect("regex match");
+        format!(
+            "{} {}",
+            &date_string[..full_match.start()],
+            &date_string[full_match.end()..]
+        )
+    }
+
+    fn utc() -> FixedOffset {
+        FixedOffset::east_opt(0).expect("zero offset")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DateParser;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    fn parse(date_string: &str) -> Option<DateTime<Utc>> {
+        let reference = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        DateParser::parse_with_reference(date_string, reference)
+    }
+
+    fn date(year: i32, month: u32, day: u32, hour: u32, min: u32) -> Option<DateTime<Utc>> {
+        Utc.with_ymd_and_hms(year, month, day, hour, min, 0)
+            .single()
+    }
+
+    #[test]
+    fn machine_formats() {
+        assert_eq!(parse("2024-03-05T10:00:00+01:00"), date(2024, 3, 5, 9, 0));
+        assert_eq!(
+            parse("Tue, 05 Mar 2024 10:00:00 GMT"),
+            date(2024, 3, 5, 10, 0)
+        );
+        assert_eq!(parse("2024-03-05T10:00:00"), date(2024, 3, 5, 10, 0));
+        assert_eq!(parse("2024-03-05 10:00:00 +0100"), date(2024, 3, 5, 9, 0));
+        assert_eq!(parse("1709632800"), date(2024, 3, 5, 10, 0));
+    }
+
+    #[test]
+    fn human_formats() {
+        assert_eq!(parse("March 5, 2024"), date(2024, 3, 5, 0, 0));
+        assert_eq!(parse("05.03.2024 14:00"), date(2024, 3, 5, 14, 0));
+        assert_eq!(parse("03/05/2024"), date(2024, 3, 5, 0, 0));
+        assert_eq!(parse("25/03/2024"), date(2024, 3, 25, 0, 0));
+        assert_eq!(
+            parse("Tuesday, March 5th, 2024 at 2:30 PM EST"),
+            date(2024, 3, 5, 19, 30)
+        );
+        assert_eq!(parse("5. März 2024, 14:00 Uhr"), date(2024, 3, 5, 14, 0));
+        assert_eq!(parse("5 de marzo de 2024"), date(2024, 3, 5, 0, 0));
+        assert_eq!(parse("1er avril 2024"), date(2024, 4, 1, 0, 0));
+        assert_eq!(parse("Dec 24"), date(2023, 12, 24, 0, 0));
+        assert_eq!(parse("not a date"), None);
+        assert_eq!(parse("Ready, set, go: 5 tips"), None);
+        assert_eq!(parse("Gen Mag photos, 2 images"), None);
+        assert_eq!(parse("may 5"), date(2024, 5, 5, 0, 0));
+        assert_eq!(parse("12 dic 2023"), date(2023, 12, 12, 0, 0));
+    }
+
+    #[test]
+    fn relative() {
+        assert_eq!(parse("3 hours ago"), date(2024, 6, 1, 9, 0));
+        assert_eq!(parse("a day ago"), date(2024, 5, 31, 12, 0));
+        assert_eq!(parse("vor 2 Tagen"), date(2024, 5, 30, 12, 0));
+        assert_eq!(parse("Yesterday at 10:15"), date(2024, 5, 31, 10, 15));
+        assert_eq!(parse("2 months ago"), date(2024, 4, 1, 12, 0));
+        assert_eq!(parse("4000000000 days ago"), None);
+        assert_eq!(parse("4000000000 weeks ago"), None);
+        assert_eq!(parse("4000000000 years ago"), None);
+        assert_eq!(parse("99999999999 days ago"), None);
+        assert_eq!(parse("vor 99999999999 Tagen"), None);
+    }
+}
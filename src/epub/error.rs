@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum EpubError {
+    #[error("No article with content")]
+    NoContent,
+    #[error("libXml Error")]
+    Xml,
+    #[error("Zip Error")]
+    Zip(#[from] zip::result::ZipError),
+    #[error("IO Error")]
+    IO(#[from] std::io::Error),
+}
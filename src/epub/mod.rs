@@ -0,0 +1,629 @@
+mod error;
+
+pub use self::error::EpubError;
+
+use crate::{article::Article, resource::Resource, util::Util, FullTextParser};
+use chrono::Utc;
+use libxml::tree::{Document, Node};
+use std::collections::HashSet;
+use std::io::{Seek, Write};
+use url::Url;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+const STYLESHEET: &str = "body { line-height: 1.5; }
+img { max-width: 100%; height: auto; }
+pre { white-space: pre-wrap; }
+p.meta { font-size: 0.9em; font-style: italic; }
+";
+
+/// Metadata of the generated book
+#[derive(Debug, Clone, Default)]
+pub struct EpubOptions {
+    /// Defaults to the title of the first article
+    pub title: Option<String>,
+    /// Defaults to the authors of all articles
+    pub author: Option<String>,
+    /// Defaults to the language of the first article or `en`
+    pub language: Option<String>,
+    /// Unique identifier of the book. Defaults to an URN derived from the article URLs.
+    pub identifier: Option<String>,
+}
+
+/// EPUB 3 writer
+///
+/// Every article with content becomes a chapter starting with its title, authors and date.
+/// Images are packaged into the book if `resolver` returns their content, all other images
+/// are removed since e-readers can't load remote resources.
+/// The navigation document lists all chapters with their `h2` & `h3` headings.
+pub struct Epub;
+
+struct Chapter {
+    file_name: String,
+    title: String,
+    headings: Vec<(String, String)>,
+    xhtml: String,
+}
+
+struct Image {
+    url: Url,
+    file_name: String,
+    resource: Resource,
+}
+
+impl Epub {
+    pub fn write<W, R>(
+        writer: W,
+        articles: &[Article],
+        options: &EpubOptions,
+        resolver: R,
+    ) -> Result<(), EpubError>
+    where
+        W: Write + Seek,
+        R: Fn(&Url) -> Option<Resource>,
+    {
+        let language = options
+            .language
+            .clone()
+            .or_else(|| articles.iter().find_map(|article| article.language.clone()))
+            .unwrap_or_else(|| "en".into());
+
+        let mut images = Vec::new();
+        let mut chapters = Vec::new();
+        for article in articles {
+            if let Some(chapter) = Self::chapter(
+                article,
+                chapters.len() + 1,
+                &language,
+                &mut images,
+                &resolver,
+            )? {
+                chapters.push(chapter);
+            }
+        }
+
+        if chapters.is_empty() {
+            return Err(EpubError::NoContent);
+        }
+
+        let title = options
+            .title
+            .clone()
+            .unwrap_or_else(|| chapters[0].title.clone());
+
+        let mut zip = ZipWriter::new(writer);
+        let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        // the mimetype has to be the first & uncompressed entry
+        zip.start_file("mimetype", stored)?;
+        zip.write_all(b"application/epub+zip")?;
+
+        zip.start_file("META-INF/container.xml", deflated)?;
+        zip.write_all(Self::container().as_bytes())?;
+
+        zip.start_file("OEBPS/content.opf", deflated)?;
+        zip.write_all(
+            Self::package(articles, options, &title, &language, &chapters, &images).as_bytes(),
+        )?;
+
+        zip.start_file("OEBPS/nav.xhtml", deflated)?;
+        zip.write_all(Self::navigation(&title, &language, &chapters).as_bytes())?;
+
+        zip.start_file("OEBPS/style.css", deflated)?;
+        zip.write_all(STYLESHEET.as_bytes())?;
+
+        for chapter in &chapters {
+            zip.start_file(format!("OEBPS/{}", chapter.file_name), deflated)?;
+            zip.write_all(chapter.xhtml.as_bytes())?;
+        }
+
+        // images are usually compressed already
+        for image in &images {
+            zip.start_file(format!("OEBPS/{}", image.file_name), stored)?;
+            zip.write_all(&image.resource.data)?;
+        }
+
+        zip.finish()?;
+        Ok(())
+    }
+
+    fn chapter<R>(
+        article: &Article,
+        number: usize,
+        language: &str,
+        images: &mut Vec<Image>,
+        resolver: &R,
+    ) -> Result<Option<Chapter>, EpubError>
+    where
+        R: Fn(&Url) -> Option<Resource>,
+    {
+        let Some(html) = article.html.as_deref() else {
+            return Ok(None);
+        };
+
+        let document = FullTextParser::parse_html_string_patched(html).map_err(|error| {
+            log::error!("Parsing article HTML failed: {error:?}");
+            EpubError::Xml
+        })?;
+        let context = FullTextParser::get_xpath_ctx(&document).map_err(|_| EpubError::Xml)?;
+
+        for xpath in [
+            "//script",
+            "//style",
+            "//noscript",
+            "//source",
+            "//object",
+            "//embed",
+            "//audio",
+            "//video",
+        ] {
+            _ = Util::strip_node(&context, xpath);
+        }
+
+        // e-readers can't play embedded videos: link to them instead
+        for mut iframe in Util::evaluate_xpath(&context, "//iframe", false).unwrap_or_default() {
+            let src = iframe.get_attribute("src").unwrap_or_default();
+            if let Some(url) = Self::resolve_url(&article.url, &src) {
+                let mut link = Node::new("a", None, &document).map_err(|()| EpubError::Xml)?;
+                _ = link.set_attribute("href", url.as_str());
+                link.set_content(url.as_str()).map_err(|_| EpubError::Xml)?;
+                iframe
+                    .add_prev_sibling(&mut link)
+                    .map_err(|_| EpubError::Xml)?;
+            }
+            iframe.unlink();
+        }
+
+        for mut img in Util::evaluate_xpath(&context, "//img", false).unwrap_or_default() {
+            let file_name = img
+                .get_attribute("src")
+                .and_then(|src| Self::resolve_url(&article.url, &src))
+                .and_then(|url| Self::package_image(url, images, resolver));
+
+            match file_name {
+                Some(file_name) => {
+                    _ = img.set_attribute("src", &file_name);
+                    _ = img.remove_attribute("srcset");
+                    _ = img.remove_attribute("sizes");
+                    _ = img.remove_attribute("loading");
+                    if img.get_attribute("alt").is_none() {
+                        _ = img.set_attribute("alt", "");
+                    }
+                }
+                None => img.unlink(),
+            }
+        }
+
+        // generated ids must not collide with ids the article already uses
+        let mut ids = Util::evaluate_xpath(&context, "//*[@id]", false)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|node| node.get_attribute("id"))
+            .collect::<HashSet<_>>();
+        let mut headings = Vec::new();
+        for mut heading in Util::evaluate_xpath(&context, "//h2 | //h3", false).unwrap_or_default()
+        {
+            let text = Util::get_inner_text(&heading, true);
+            if text.is_empty() {
+                continue;
+            }
+
+            let id = match heading.get_attribute("id") {
+                Some(id) if !id.trim().is_empty() => id,
+                _ => {
+                    let id = (headings.len() + 1..)
+                        .map(|index| format!("heading-{index}"))
+                        .find(|id| !ids.contains(id))
+                        .unwrap_or_default();
+                    _ = heading.set_attribute("id", &id);
+                    ids.insert(id.clone());
+                    id
+                }
+            };
+            headings.push((id, text));
+        }
+
+        let content = Self::serialize_body(&document)?;
+
+        let title = article
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("Chapter {number}"));
+        let meta = [
+            article.author.clone(),
+            article.site_name.clone(),
+            article.date.map(|date| date.format("%Y-%m-%d").to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|part| escaper::encode_minimal(&part))
+        .chain(std::iter::once(format!(
+            "<a href=\"{0}\">{0}</a>",
+            escaper::encode_minimal(article.url.as_str())
+        )))
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+        let direction = article
+            .direction
+            .as_deref()
+            .map(|direction| format!(" dir=\"{}\"", escaper::encode_minimal(direction)))
+            .unwrap_or_default();
+        let chapter_language = article.language.as_deref().unwrap_or(language);
+
+        let xhtml = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops" lang="{language}" xml:lang="{language}">
+<head>
+<meta charset="UTF-8"/>
+<title>{title}</title>
+<link rel="stylesheet" type="text/css" href="style.css"/>
+</head>
+<body{direction}>
+<h1>{title}</h1>
+<p class="meta">{meta}</p>
+{content}
+</body>
+</html>
+"#,
+            language = escaper::encode_minimal(chapter_language),
+            title = escaper::encode_minimal(&title),
+        );
+
+        Ok(Some(Chapter {
+            file_name: format!("chapter-{number}.xhtml"),
+            title,
+            headings,
+            xhtml,
+        }))
+    }
+
+    // returns the file name of the packaged image
+    fn package_image<R>(url: Url, images: &mut Vec<Image>, resolver: &R) -> Option<String>
+    where
+        R: Fn(&Url) -> Option<Resource>,
+    {
+        if let Some(image) = images.iter().find(|image| image.url == url) {
+            return Some(image.file_name.clone());
+        }
+
+        let Some(resource) = resolver(&url).filter(Resource::is_image) else {
+            log::debug!("No image content for '{url}'");
+            return None;
+        };
+
+        let file_name = format!("images/image-{}.{}", images.len() + 1, resource.extension());
+        images.push(Image {
+            url,
+            file_name: file_name.clone(),
+            resource,
+        });
+        Some(file_name)
+    }
+
+    fn resolve_url(base_url: &Url, url: &str) -> Option<Url> {
+        let url = url.trim();
+        if url.is_empty() || url.starts_with("data:") {
+            return None;
+        }
+        base_url.join(url).ok()
+    }
+
+    // serialize the content of `<body>` as XML
+    fn serialize_body(document: &Document) -> Result<String, EpubError> {
+        let body = document
+            .get_root_element()
+            .and_then(|root| Util::get_first_element_by_tag_name(&root, "body"))
+            .ok_or(EpubError::Xml)?;
+
+        let mut xml_document = Document::new().map_err(|()| EpubError::Xml)?;
+        let mut root = Node::new("div", None, &xml_document).map_err(|()| EpubError::Xml)?;
+        xml_document.set_root_element(&root);
+
+        for mut child in body.get_child_nodes() {
+            child.unlink();
+            root.add_child(&mut child).map_err(|error| {
+                log::error!("{error}");
+                EpubError::Xml
+            })?;
+        }
+
+        Ok(root
+            .get_child_nodes()
+            .iter()
+            .map(|child| Util::serialize_node(&xml_document, child))
+            .collect())
+    }
+
+    fn container() -> String {
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+<rootfiles>
+<rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+</rootfiles>
+</container>
+"#
+        .into()
+    }
+
+    fn package(
+        articles: &[Article],
+        options: &EpubOptions,
+        title: &str,
+        language: &str,
+        chapters: &[Chapter],
+        images: &[Image],
+    ) -> String {
+        let identifier = options.identifier.clone().unwrap_or_else(|| {
+            let urls = articles
+                .iter()
+                .map(|article| article.url.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("urn:article-extractor:{:016x}", Util::stable_hash(&urls))
+        });
+
+        let mut creators = Vec::new();
+        match options.author.as_deref() {
+            Some(author) => creators.push(author.to_string()),
+            None => {
+                for article in articles {
+                    let names = if article.authors.is_empty() {
+                        article.author.iter().cloned().collect::<Vec<_>>()
+                    } else {
+                        article
+                            .authors
+                            .iter()
+                            .map(|author| author.name.clone())
+                            .collect()
+                    };
+                    for name in names {
+                        if !creators.contains(&name) {
+                            creators.push(name);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut metadata = vec![
+            format!(
+                "<dc:identifier id=\"book-id\">{}</dc:identifier>",
+                escaper::encode_minimal(&identifier)
+            ),
+            format!("<dc:title>{}</dc:title>", escaper::encode_minimal(title)),
+            format!(
+                "<dc:language>{}</dc:language>",
+                escaper::encode_minimal(language)
+            ),
+        ];
+        metadata.extend(creators.iter().map(|creator| {
+            format!(
+                "<dc:creator>{}</dc:creator>",
+                escaper::encode_minimal(creator)
+            )
+        }));
+
+        // details of the source are only meaningful for a single article
+        if let [article] = articles {
+            if let Some(date) = article.date {
+                metadata.push(format!(
+                    "<dc:date>{}</dc:date>",
+                    date.format("%Y-%m-%dT%H:%M:%SZ")
+                ));
+            }
+            if let Some(site_name) = article.site_name.as_deref() {
+                metadata.push(format!(
+                    "<dc:publisher>{}</dc:publisher>",
+                    escaper::encode_minimal(site_name)
+                ));
+            }
+            if let Some(description) = article.description.as_deref() {
+                metadata.push(format!(
+                    "<dc:description>{}</dc:description>",
+                    escaper::encode_minimal(description)
+                ));
+            }
+            metadata.push(format!(
+                "<dc:source>{}</dc:source>",
+                escaper::encode_minimal(article.url.as_str())
+            ));
+        }
+
+        // keep the book reproducible: only fall back to the current time without any dates
+        let modified = articles
+            .iter()
+            .filter_map(|article| article.date_modified.or(article.date))
+            .max()
+            .unwrap_or_else(Utc::now);
+        metadata.push(format!(
+            "<meta property=\"dcterms:modified\">{}</meta>",
+            modified.format("%Y-%m-%dT%H:%M:%SZ")
+        ));
+
+        let mut manifest = vec![
+            r#"<item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>"#.to_string(),
+            r#"<item id="style" href="style.css" media-type="text/css"/>"#.to_string(),
+        ];
+        manifest.extend(chapters.iter().enumerate().map(|(index, chapter)| {
+            let properties = if chapter.xhtml.contains("<svg") {
+                " properties=\"svg\""
+            } else {
+                ""
+            };
+            format!(
+                "<item id=\"chapter-{}\" href=\"{}\" media-type=\"application/xhtml+xml\"{properties}/>",
+                index + 1,
+                chapter.file_name
+            )
+        }));
+        manifest.extend(images.iter().enumerate().map(|(index, image)| {
+            format!(
+                "<item id=\"image-{}\" href=\"{}\" media-type=\"{}\"/>",
+                index + 1,
+                image.file_name,
+                escaper::encode_minimal(image.resource.essence())
+            )
+        }));
+
+        let spine = (1..=chapters.len())
+            .map(|number| format!("<itemref idref=\"chapter-{number}\"/>"))
+            .collect::<Vec<_>>();
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="book-id" xml:lang="{language}">
+<metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+{metadata}
+</metadata>
+<manifest>
+{manifest}
+</manifest>
+<spine>
+{spine}
+</spine>
+</package>
+"#,
+            language = escaper::encode_minimal(language),
+            metadata = metadata.join("\n"),
+            manifest = manifest.join("\n"),
+            spine = spine.join("\n"),
+        )
+    }
+
+    fn navigation(title: &str, language: &str, chapters: &[Chapter]) -> String {
+        let entries = chapters
+            .iter()
+            .map(|chapter| {
+                let headings = if chapter.headings.is_empty() {
+                    String::new()
+                } else {
+                    let headings = chapter
+                        .headings
+                        .iter()
+                        .map(|(id, text)| {
+                            format!(
+                                "<li><a href=\"{}#{}\">{}</a></li>",
+                                chapter.file_name,
+                                escaper::encode_minimal(id),
+                                escaper::encode_minimal(text)
+                            )
+                        })
+                        .collect::<String>();
+                    format!("<ol>{headings}</ol>")
+                };
+
+                format!(
+                    "<li><a href=\"{}\">{}</a>{headings}</li>",
+                    chapter.file_name,
+                    escaper::encode_minimal(&chapter.title)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops" lang="{language}" xml:lang="{language}">
+<head>
+<meta charset="UTF-8"/>
+<title>{title}</title>
+</head>
+<body>
+<nav epub:type="toc" id="toc">
+<h1>{title}</h1>
+<ol>
+{entries}
+</ol>
+</nav>
+</body>
+</html>
+"#,
+            language = escaper::encode_minimal(language),
+            title = escaper::encode_minimal(title),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Epub, EpubOptions};
+    use crate::{article::Article, resource::Resource};
+    use chrono::{TimeZone, Utc};
+    use std::io::{Cursor, Read};
+    use url::Url;
+
+    #[test]
+    fn write_book() {
+        let mut article = Article::new(Url::parse("https://example.com/posts/1").unwrap());
+        article.title = Some("First & Last".into());
+        article.author = Some("Jane Doe".into());
+        article.date = Some(Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap());
+        article.html = Some(
+            r#"<article><h2>Intro</h2><iframe src="https://video.example.com/embed/1"></iframe><p>Text<br>more</p><img src="/a.png" alt="A"><img src="/missing.png"><h3 id="end">End</h3></article>"#
+                .into(),
+        );
+
+        let mut buffer = Cursor::new(Vec::new());
+        Epub::write(&mut buffer, &[article], &EpubOptions::default(), |url| {
+            (url.path() == "/a.png").then(|| Resource::new(vec![1, 2, 3], "image/png"))
+        })
+        .unwrap();
+
+        let mut archive = zip::ZipArchive::new(buffer).unwrap();
+        assert_eq!(archive.by_index(0).unwrap().name(), "mimetype");
+
+        let mut read = |name: &str| {
+            let mut content = String::new();
+            archive
+                .by_name(name)
+                .unwrap()
+                .read_to_string(&mut content)
+                .unwrap();
+            content
+        };
+
+        let chapter = read("OEBPS/chapter-1.xhtml");
+        assert!(chapter.contains("<title>First &amp; Last</title>"));
+        assert!(chapter.contains("<h2 id=\"heading-1\">Intro</h2>"));
+        assert!(chapter.contains("<br/>"));
+        assert!(chapter.contains("src=\"images/image-1.png\""));
+        assert!(!chapter.contains("missing.png"));
+        assert!(chapter.contains(
+            "<a href=\"https://video.example.com/embed/1\">https://video.example.com/embed/1</a>"
+        ));
+
+        let navigation = read("OEBPS/nav.xhtml");
+        assert!(navigation.contains("<a href=\"chapter-1.xhtml#end\">End</a>"));
+
+        let package = read("OEBPS/content.opf");
+        assert!(package.contains("<dc:source>https://example.com/posts/1</dc:source>"));
+        assert!(package.contains("media-type=\"image/png\""));
+        assert!(package.contains("<meta property=\"dcterms:modified\">2024-05-01T12:00:00Z</meta>"));
+        assert!(package.contains("<dc:creator>Jane Doe</dc:creator>"));
+    }
+
+    #[test]
+    fn heading_ids_skip_existing_ids() {
+        let mut article = Article::new(Url::parse("https://example.com/posts/2").unwrap());
+        article.html =
+            Some(r#"<article><h2>One</h2><p id="heading-1">Text</p><h2>Two</h2></article>"#.into());
+
+        let mut buffer = Cursor::new(Vec::new());
+        Epub::write(&mut buffer, &[article], &EpubOptions::default(), |_| None).unwrap();
+
+        let mut archive = zip::ZipArchive::new(buffer).unwrap();
+        let mut chapter = String::new();
+        archive
+            .by_name("OEBPS/chapter-1.xhtml")
+            .unwrap()
+            .read_to_string(&mut chapter)
+            .unwrap();
+
+        assert!(chapter.contains("<h2 id=\"heading-2\">One</h2>"));
+        assert!(chapter.contains("<p id=\"heading-1\">Text</p>"));
+        assert!(chapter.contains("<h2 id=\"heading-3\">Two</h2>"));
+    }
+}
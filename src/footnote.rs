@@ -0,0 +1,432 @@
+use crate::{
+    constants,
+    full_text_parser::{config::ConfigEntry, error::FullTextParserError},
+    util::Util,
+};
+use libxml::{
+    tree::{Document, Node},
+    xpath::Context,
+};
+use url::Url;
+
+/// A footnote or endnote referenced from the article body
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Footnote {
+    /// `id` of the note. Markers in the article link to it via `#id`.
+    pub id: String,
+    /// Text of the first marker referencing the note (usually a number)
+    pub label: Option<String>,
+    /// Inner HTML of the note without back-references
+    pub html: String,
+    /// Plain text content of the note
+    pub text: String,
+}
+
+impl Footnote {
+    /// Collect all footnotes of a page and detach them from the document.
+    ///
+    /// The notes are moved into a `<section role="doc-endnotes">` appended to `root`,
+    /// which is returned so the caller can move it behind the extracted body.
+    /// Markers referencing the notes are tagged with `role="doc-noteref"`.
+    pub(crate) fn extract(
+        context: &Context,
+        document: &Document,
+        root: &mut Node,
+        config: Option<&ConfigEntry>,
+        global_config: Option<&ConfigEntry>,
+        url: &Url,
+    ) -> Result<(Vec<Footnote>, Option<Node>), FullTextParserError> {
+        let enabled = config
+            .and_then(|config| config.footnotes)
+            .or_else(|| global_config.and_then(|config| config.footnotes));
+        if enabled == Some(false) {
+            return Ok((Vec::new(), None));
+        }
+
+        let configured_notes = Self::configured_notes(context, config, global_config);
+        let markers = Util::evaluate_xpath(context, constants::FOOTNOTE_MARKER_XPATH, false)?;
+
+        let mut notes: Vec<(Node, Option<String>)> = Vec::new();
+        let mut marker_ids = Vec::new();
+
+        for mut marker in markers {
+            let Some(id) = marker
+                .get_attribute("href")
+                .and_then(|href| href.trim().strip_prefix('#').map(String::from))
+            else {
+                continue;
+            };
+
+            if id.is_empty() || id.contains('\'') {
+                continue;
+            }
+
+            if let Some((_note, label)) = notes
+                .iter_mut()
+                .find(|(note, _label)| note.get_attribute("id").as_deref() == Some(id.as_str()))
+            {
+                if label.is_none() {
+                    label.replace(marker.get_content().trim().into());
+                }
+                _ = marker.set_attribute("role", "doc-noteref");
+                continue;
+            }
+
+            let Some(target) = Util::evaluate_xpath(context, &format!("//*[@id='{id}']"), false)?
+                .into_iter()
+                .next()
+            else {
+                continue;
+            };
+
+            let is_note = if configured_notes.is_empty() {
+                Self::is_probably_note(&target)
+            } else {
+                configured_notes.contains(&target)
+            };
+
+            if !is_note || Util::get_node_ancestors(&marker, None).contains(&target) {
+                continue;
+            }
+
+            if let Some(marker_id) = marker.get_attribute("id") {
+                marker_ids.push(marker_id);
+            }
+            _ = marker.set_attribute("role", "doc-noteref");
+
+            let label = marker.get_content().trim().to_string();
+            let label = if label.is_empty() { None } else { Some(label) };
+            notes.push((target, label));
+        }
+
+        // configured notes without any marker are kept as well
+        for note in configured_notes {
+            if note.get_attribute("id").is_some() && !notes.iter().any(|(n, _)| n == &note) {
+                notes.push((note, None));
+            }
+        }
+
+        if notes.is_empty() {
+            return Ok((Vec::new(), None));
+        }
+
+        let mut section = root
+            .new_child(None, "section")
+            .map_err(|_| FullTextParserError::Xml)?;
+        section
+            .set_attribute("role", "doc-endnotes")
+            .map_err(|_| FullTextParserError::Xml)?;
+        let mut list = section
+            .new_child(None, "ol")
+            .map_err(|_| FullTextParserError::Xml)?;
+
+        let mut footnotes = Vec::new();
+
+        for (mut note, label) in notes {
+            if note.is_null() {
+                continue;
+            }
+
+            Self::remove_backlinks(&note, &marker_ids);
+            Self::complete_urls(&note, url);
+
+            let html = note
+                .get_child_nodes()
+                .iter()
+                .map(|child| document.node_to_string(child))
+                .collect::<String>();
+
+            footnotes.push(Footnote {
+                id: note.get_attribute("id").unwrap_or_default(),
+                label,
+                html: html.trim().into(),
+                text: Util::get_inner_text(&note, true),
+            });
+
+            let parent = note.get_parent();
+            note.unlink();
+            Self::remove_empty_ancestors(parent);
+
+            if note.get_name().to_uppercase() != "LI" {
+                _ = note.set_name("li");
+            }
+            _ = note.set_attribute("role", "doc-endnote");
+            list.add_child(&mut note).map_err(|error| {
+                log::error!("{error}");
+                FullTextParserError::Xml
+            })?;
+        }
+
+        Ok((footnotes, Some(section)))
+    }
+
+    /// Move the notes of `other` into the list of `section` and drop `other`.
+    pub(crate) fn merge_sections(
+        section: &Node,
+        mut other: Node,
+    ) -> Result<(), FullTextParserError> {
+        let Some(mut list) = section.get_first_element_child() else {
+            return Err(FullTextParserError::Xml);
+        };
+
+        for other_list in other.get_child_elements() {
+            for mut note in other_list.get_child_elements() {
+                note.unlink();
+                list.add_child(&mut note).map_err(|error| {
+                    log::error!("{error}");
+                    FullTextParserError::Xml
+                })?;
+            }
+        }
+
+        other.unlink();
+        Ok(())
+    }
+
+    fn configured_notes(
+        context: &Context,
+        config: Option<&ConfigEntry>,
+        global_config: Option<&ConfigEntry>,
+    ) -> Vec<Node> {
+        let mut notes = Vec::new();
+
+        // check site specific config
+        if let Some(config) = config {
+            for xpath_footnotes in &config.xpath_footnotes {
+                if let Ok(nodes) = Util::evaluate_xpath(context, xpath_footnotes, false) {
+                    notes.extend(nodes);
+                }
+            }
+        }
+
+        // check global config
+        if notes.is_empty() {
+            if let Some(global_config) = global_config {
+                for xpath_footnotes in &global_config.xpath_footnotes {
+                    if let Ok(nodes) = Util::evaluate_xpath(context, xpath_footnotes, false) {
+                        notes.extend(nodes);
+                    }
+                }
+            }
+        }
+
+        notes
+    }
+
+    fn is_probably_note(node: &Node) -> bool {
+        let tag_name = node.get_name().to_uppercase();
+        let role = node.get_attribute("role").unwrap_or_default();
+        let parent_signature = node
+            .get_parent()
+            .map(|parent| Util::get_signature(&parent))
+            .unwrap_or_default();
+
+        let looks_like_note = role == "doc-endnote"
+            || role == "doc-footnote"
+            || constants::FOOTNOTE.is_match(&Util::get_signature(node))
+            || (tag_name == "LI" && constants::FOOTNOTE.is_match(&parent_signature));
+
+        looks_like_note && Util::get_inner_text(node, true).len() < constants::FOOTNOTE_MAX_LENGTH
+    }
+
+    fn remove_backlinks(note: &Node, marker_ids: &[String]) {
+        for mut link in Util::get_elements_by_tag_name(note, "a") {
+            let Some(href) = link.get_attribute("href") else {
+                continue;
+            };
+            let Some(target) = href.trim().strip_prefix('#') else {
+                continue;
+            };
+
+            let is_backlink = link.get_attribute("role").as_deref() == Some("doc-backlink")
+                || link
+                    .get_attribute("class")
+                    .map(|class| class.contains("back"))
+                    .unwrap_or(false)
+                || marker_ids.iter().any(|id| id == target)
+                || constants::FOOTNOTE_BACKLINK_TEXT.contains(&link.get_content().trim());
+
+            if is_backlink {
+                link.unlink();
+            }
+        }
+    }
+
+    fn complete_urls(note: &Node, url: &Url) {
+        for (tag, attribute) in [("a", "href"), ("img", "src")] {
+            for mut node in Util::get_elements_by_tag_name(note, tag) {
+                let Some(value) = node.get_attribute(attribute) else {
+                    continue;
+                };
+                let value = value.trim();
+
+                if value.starts_with('#') {
+                    continue;
+                }
+
+                let is_relative_url = Url::parse(value)
+                    .err()
+                    .map(|err| err == url::ParseError::RelativeUrlWithoutBase)
+                    .unwrap_or(false);
+                if is_relative_url {
+                    if let Ok(completed_url) = url.join(value) {
+                        _ = node.set_attribute(attribute, completed_url.as_str());
+                    }
+                }
+            }
+        }
+    }
+
+    fn remove_empty_ancestors(node: Option<Node>) {
+        let mut node = node;
+
+        while let Some(mut parent) = node {
+            let tag_name = parent.get_name().to_uppercase();
+            if tag_name == "BODY"
+                || tag_name == "HTML"
+                || !parent.get_child_elements().is_empty()
+                || !parent.get_content().trim().is_empty()
+            {
+                break;
+            }
+
+            node = parent.get_parent();
+            parent.unlink();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Footnote;
+    use crate::{full_text_parser::config::ConfigEntry, FullTextParser};
+    use libxml::tree::{Document, Node};
+    use url::Url;
+
+    #[test]
+    fn heuristic_endnotes() {
+        libxml::tree::node::set_node_rc_guard(10);
+
+        let html = r##"<html><body>
+            <p>Some claim<sup id="fnref1"><a href="#fn1">1</a></sup> and another<sup><a href="#fn2">2</a></sup>.</p>
+            <div class="footnotes"><ol>
+                <li id="fn1"><p>First <a href="/source">source</a>. <a href="#fnref1">↩</a></p></li>
+                <li id="fn2"><p>Second note.</p></li>
+            </ol></div>
+        </body></html>"##;
+
+        let document = FullTextParser::parse_html_string_patched(html).unwrap();
+        let context = FullTextParser::get_xpath_ctx(&document).unwrap();
+        let url = Url::parse("https://example.com/post/").unwrap();
+
+        let article_document = Document::new().unwrap();
+        let mut root = Node::new("article", None, &article_document).unwrap();
+
+        let (footnotes, section) =
+            Footnote::extract(&context, &document, &mut root, None, None, &url).unwrap();
+
+        assert_eq!(footnotes.len(), 2);
+        assert_eq!(footnotes[0].id, "fn1");
+        assert_eq!(footnotes[0].label.as_deref(), Some("1"));
+        assert_eq!(footnotes[0].text, "First source.");
+        assert!(footnotes[0]
+            .html
+            .contains("<a href=\"https://example.com/source\">source</a>"));
+        assert!(!footnotes[0].html.contains("fnref1"));
+        assert_eq!(footnotes[1].text, "Second note.");
+
+        // the original endnote list is gone from the page
+        assert!(
+            crate::util::Util::evaluate_xpath(&context, "//div[@class='footnotes']", false)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(section.is_some());
+    }
+
+    #[test]
+    fn ignore_unrelated_anchors() {
+        libxml::tree::node::set_node_rc_guard(10);
+
+        let html = r##"<html><body>
+            <p id="intro">Text<sup><a href="#intro">top</a></sup></p>
+            <p>More<sup><a href="#notebook">1</a></sup></p>
+            <div id="notebook">A notebook, not a note.</div>
+        </body></html>"##;
+
+        let document = FullTextParser::parse_html_string_patched(html).unwrap();
+        let context = FullTextParser::get_xpath_ctx(&document).unwrap();
+        let url = Url::parse("https://example.com/post/").unwrap();
+
+        let article_document = Document::new().unwrap();
+        let mut root = Node::new("article", None, &article_document).unwrap();
+
+        let (footnotes, section) =
+            Footnote::extract(&context, &document, &mut root, None, None, &url).unwrap();
+        assert!(footnotes.is_empty());
+        assert!(section.is_none());
+    }
+
+    #[test]
+    fn disabled_in_global_config() {
+        libxml::tree::node::set_node_rc_guard(10);
+
+        let html = r##"<html><body>
+            <p>Some claim<sup><a href="#fn1">1</a></sup>.</p>
+            <ol class="footnotes"><li id="fn1">First source.</li></ol>
+        </body></html>"##;
+
+        let document = FullTextParser::parse_html_string_patched(html).unwrap();
+        let context = FullTextParser::get_xpath_ctx(&document).unwrap();
+        let url = Url::parse("https://example.com/post/").unwrap();
+
+        let article_document = Document::new().unwrap();
+        let mut root = Node::new("article", None, &article_document).unwrap();
+
+        let global_config = ConfigEntry {
+            footnotes: Some(false),
+            ..Default::default()
+        };
+        let (footnotes, section) = Footnote::extract(
+            &context,
+            &document,
+            &mut root,
+            None,
+            Some(&global_config),
+            &url,
+        )
+        .unwrap();
+        assert!(footnotes.is_empty());
+        assert!(section.is_none());
+    }
+
+    #[test]
+    fn single_section_for_multiple_pages() {
+        let page = |number: usize| {
+            format!(
+                r##"<html><body>
+            <div class="post"><p>Claim number {number} is backed by a source<sup><a href="#fn{number}">{number}</a></sup> that is listed below the text of this page.</p></div>
+            <ol class="footnotes"><li id="fn{number}">Source of page {number}.</li></ol>
+        </body></html>"##
+            )
+        };
+        let config = ConfigEntry {
+            xpath_body: vec!["//div[@class='post']".into()],
+            ..Default::default()
+        };
+
+        let parser = FullTextParser::new(None);
+        let article = parser
+            .parse_offline(vec![page(1), page(2)], Some(&config), None)
+            .unwrap();
+        let html = article.html.unwrap();
+
+        assert_eq!(article.footnotes.len(), 2);
+        assert_eq!(html.matches("doc-endnotes").count(), 1);
+        let section = html.find("doc-endnotes").unwrap();
+        assert!(html.rfind("Claim number 2").unwrap() < section);
+        assert!(html[section..].contains("Source of page 1."));
+        assert!(html[section..].contains("Source of page 2."));
+    }
+}
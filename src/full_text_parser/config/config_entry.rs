@@ -7,24 +7,31 @@ use std::io::{BufRead, BufReader, Cursor, Read};
 use std::path::Path;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Replace {
     pub to_replace: String,
     pub replace_with: String,
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Header {
     pub name: String,
     pub value: String,
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct ConfigEntry {
     pub xpath_title: Vec<String>,
     pub xpath_author: Vec<String>,
     pub xpath_date: Vec<String>,
     pub xpath_body: Vec<String>,
     pub xpath_strip: Vec<String>,
+    pub xpath_footnotes: Vec<String>,
+    pub footnotes: Option<bool>,
+    pub skip_json_ld: bool,
     pub strip_id_or_class: Vec<String>,
     pub strip_image_src: Vec<String>,
     pub replace: Vec<Replace>,
@@ -55,6 +62,9 @@ impl ConfigEntry {
         let mut xpath_date: Vec<String> = Vec::new();
         let mut xpath_body: Vec<String> = Vec::new();
         let mut xpath_strip: Vec<String> = Vec::new();
+        let mut xpath_footnotes: Vec<String> = Vec::new();
+        let mut footnotes_enabled: Option<bool> = None;
+        let mut skip_json_ld = false;
         let mut strip_id_or_class: Vec<String> = Vec::new();
         let mut strip_image_src: Vec<String> = Vec::new();
         let mut replace_vec: Vec<Replace> = Vec::new();
@@ -76,6 +86,8 @@ impl ConfigEntry {
         let replace = "replace_string:";
         let replace_single = "replace_string(";
         let http_header = "http_header(";
+        let footnotes = "footnotes:";
+        let json_ld = "skip_json_ld:";
 
         // ignore these
         let tidy = "tidy:";
@@ -109,6 +121,27 @@ impl ConfigEntry {
             extract_option_single!(line, single_page, single_page_link);
             extract_option_single!(line, next_page, next_page_link);
 
+            // either a yes/no switch or XPaths selecting the notes
+            if line.starts_with(footnotes) {
+                let value = Util::str_extract_value(footnotes, line);
+                match parse_bool(value) {
+                    Some(enabled) => footnotes_enabled = Some(enabled),
+                    None => xpath_footnotes.extend(
+                        Util::split_values(value)
+                            .into_iter()
+                            .map(|s| s.trim().to_string()),
+                    ),
+                }
+
+                continue;
+            }
+
+            if line.starts_with(json_ld) {
+                let value = Util::str_extract_value(json_ld, line);
+                skip_json_ld = parse_bool(value).unwrap_or(false);
+                continue;
+            }
+
             if line.starts_with(replace_single) {
                 let value = Util::str_extract_value(replace_single, line);
                 let value: Vec<&str> = value.split("): ").map(|s| s.trim()).collect();
@@ -169,6 +202,9 @@ impl ConfigEntry {
             xpath_date,
             xpath_body,
             xpath_strip,
+            xpath_footnotes,
+            footnotes: footnotes_enabled,
+            skip_json_ld,
             strip_id_or_class,
             strip_image_src,
             replace: replace_vec,
@@ -180,3 +216,11 @@ impl ConfigEntry {
         Ok(config)
     }
 }
+
+pub(super) fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "yes" | "true" => Some(true),
+        "no" | "false" => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,285 @@
+use super::config_entry::parse_bool;
+use super::{error::ConfigError, ConfigEntry};
+use crate::util::Util;
+use std::ffi::CString;
+use std::fmt;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+// directives of ftr-site-config that are parsed but not (yet) supported
+const UNSUPPORTED_DIRECTIVES: &[&str] = &[
+    "autodetect_next_page",
+    "convert_double_br_tags",
+    "dissolve",
+    "if_page_contains",
+    "insert_detected_image",
+    "login_extra_fields",
+    "login_password_field",
+    "login_uri",
+    "login_username_field",
+    "move_into",
+    "native_ad_clue",
+    "not_logged_in_xpath",
+    "parser",
+    "requires_login",
+    "single_page_link_in_feed",
+    "skip_id_or_class",
+    "src_lazy_load_attr",
+    "strip_attr",
+    "test_contains",
+    "wrap_in",
+];
+
+// directives ignored on purpose
+const IGNORED_DIRECTIVES: &[&str] = &["tidy", "prune", "test_url", "autodetect_on_failure"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    /// The line is ignored or won't work as intended
+    Error,
+    /// The line is valid ftr-site-config syntax but has no effect
+    Warning,
+}
+
+/// Problem in a site config found by [`ConfigEntry::lint`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigDiagnostic {
+    /// 1-based line number
+    pub line: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: {severity}: {}", self.line, self.message)
+    }
+}
+
+/// Number of nodes an XPath of a site config matched in a page.
+/// Returned by [`FullTextParser::match_config_xpaths`](crate::FullTextParser::match_config_xpaths).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XPathMatch {
+    /// Directive the XPath belongs to: `title`, `body`, `date` or `author`
+    pub directive: String,
+    pub xpath: String,
+    pub count: usize,
+}
+
+impl ConfigEntry {
+    pub fn lint_path(config_path: &Path) -> Result<Vec<ConfigDiagnostic>, ConfigError> {
+        let file = std::fs::File::open(config_path)?;
+        Self::lint(file)
+    }
+
+    /// Check a site config for lines the parser ignores or misinterprets:
+    /// unknown directives, invalid XPath expressions, malformed replacements & headers,
+    /// `find_string` without `replace_string` and invalid yes/no values.
+    pub fn lint<R: Read>(reader: R) -> Result<Vec<ConfigDiagnostic>, ConfigError> {
+        let mut diagnostics = Vec::new();
+        let mut error = |line: usize, message: String| {
+            diagnostics.push(ConfigDiagnostic {
+                line,
+                severity: Severity::Error,
+                message,
+            })
+        };
+        let mut warnings = Vec::new();
+        let mut open_find_string: Option<usize> = None;
+
+        for (index, line) in BufReader::new(reader).lines().enumerate() {
+            let number = index + 1;
+            let line = line?;
+            let line = line.trim();
+
+            // the parser takes the line directly following `find_string` as replacement
+            if let Some(find_line) = open_find_string.take() {
+                if line.starts_with("replace_string:") {
+                    continue;
+                }
+                error(
+                    find_line,
+                    "find_string is not directly followed by replace_string".into(),
+                );
+            }
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (directive, value) = match line.split_once(':') {
+                Some((directive, _)) if directive.contains('(') => {
+                    // `replace_string(...)` & `http_header(...)`
+                    let (directive, _) = directive.split_once('(').unwrap_or_default();
+                    let value = &line[directive.len() + 1..];
+                    if value.split("): ").count() != 2 {
+                        error(number, format!("expected '{directive}(<name>): <value>'"));
+                        continue;
+                    }
+                    if directive != "replace_string" && directive != "http_header" {
+                        warnings.push((number, format!("unknown directive '{directive}'")));
+                    }
+                    continue;
+                }
+                Some((directive, _)) => (
+                    directive.trim(),
+                    Util::str_extract_value(&format!("{directive}:"), line),
+                ),
+                None => {
+                    error(number, format!("expected '<directive>: <value>': '{line}'"));
+                    continue;
+                }
+            };
+
+            match directive {
+                "title" | "body" | "date" | "author" => {
+                    for xpath in Util::split_values(value) {
+                        if let Some(message) = Self::check_xpath(xpath) {
+                            error(number, message);
+                        }
+                    }
+                }
+                "strip" | "single_page_link" | "next_page_link" => {
+                    if let Some(message) = Self::check_xpath(value) {
+                        error(number, message);
+                    }
+                }
+                "footnotes" if parse_bool(value).is_none() => {
+                    for xpath in Util::split_values(value) {
+                        if let Some(message) = Self::check_xpath(xpath) {
+                            error(number, message);
+                        }
+                    }
+                }
+                "skip_json_ld" if parse_bool(value).is_none() => {
+                    error(number, format!("expected yes or no: '{value}'"));
+                }
+                "strip_id_or_class" | "strip_image_src" if value.is_empty() => {
+                    error(number, format!("{directive} without value"));
+                }
+                "find_string" => open_find_string = Some(number),
+                "replace_string" => error(number, "replace_string without find_string".into()),
+                "footnotes" | "skip_json_ld" | "strip_id_or_class" | "strip_image_src" => {}
+                _ if IGNORED_DIRECTIVES.contains(&directive) => {}
+                _ if UNSUPPORTED_DIRECTIVES.contains(&directive) => {
+                    warnings.push((number, format!("unsupported directive '{directive}'")));
+                }
+                _ => warnings.push((number, format!("unknown directive '{directive}'"))),
+            }
+        }
+
+        if let Some(find_line) = open_find_string {
+            error(find_line, "find_string without replace_string".into());
+        }
+
+        diagnostics.extend(
+            warnings
+                .into_iter()
+                .map(|(line, message)| ConfigDiagnostic {
+                    line,
+                    severity: Severity::Warning,
+                    message,
+                }),
+        );
+        diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+        Ok(diagnostics)
+    }
+
+    fn check_xpath(xpath: &str) -> Option<String> {
+        if xpath.is_empty() {
+            return Some("empty XPath".into());
+        }
+
+        let Ok(expression) = CString::new(xpath) else {
+            return Some(format!("invalid XPath '{xpath}'"));
+        };
+
+        // compiling doesn't need a document to evaluate against
+        let compiled =
+            unsafe { libxml::bindings::xmlXPathCompile(expression.as_ptr() as *const u8) };
+        if compiled.is_null() {
+            return Some(format!("invalid XPath '{xpath}'"));
+        }
+
+        unsafe { libxml::bindings::xmlXPathFreeCompExpr(compiled) };
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Severity;
+    use crate::full_text_parser::config::ConfigEntry;
+    use crate::FullTextParser;
+    use std::borrow::Cow;
+
+    #[test]
+    fn lint() {
+        let config = "# comment
+title: //h1[@class='title'] | //h1[
+body: //article
+strip_id_or_class:
+find_string: <p>
+date: //time
+skip_json_ld: maybe
+replace_string(<br>) <br/>
+http_header(user-agent): curl
+prune: no
+native_ad_clue: //div
+some_typo: //div
+";
+
+        let diagnostics = ConfigEntry::lint(config.as_bytes()).unwrap();
+        let lines = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.severity))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            lines,
+            vec![
+                (2, Severity::Error),
+                (4, Severity::Error),
+                (5, Severity::Error),
+                (7, Severity::Error),
+                (8, Severity::Error),
+                (11, Severity::Warning),
+                (12, Severity::Warning),
+            ]
+        );
+        assert_eq!(diagnostics[0].message, "invalid XPath '//h1['");
+    }
+
+    #[test]
+    fn match_config_xpaths() {
+        let config = ConfigEntry::parse_data(Cow::Borrowed(
+            b"title: //h1\nbody: //div[@id='content'] | //article\n",
+        ))
+        .unwrap();
+        let parser = FullTextParser::new(None);
+        let html = "<html><body><h1>Title</h1><article><p>Text</p></article></body></html>";
+
+        let matches = parser
+            .match_config_xpaths(&config, html)
+            .unwrap()
+            .into_iter()
+            .map(|m| (m.directive, m.xpath, m.count))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            matches,
+            vec![
+                ("title".to_string(), "//h1".to_string(), 1),
+                ("body".to_string(), "//div[@id='content']".to_string(), 0),
+                ("body".to_string(), "//article".to_string(), 1),
+            ]
+        );
+    }
+}
@@ -3,7 +3,9 @@ mod macros;
 mod config_collection;
 mod config_entry;
 mod error;
+mod lint;
 
 pub use config_collection::ConfigCollection;
 pub use config_entry::ConfigEntry;
 pub use error::ConfigError;
+pub use lint::{ConfigDiagnostic, Severity, XPathMatch};
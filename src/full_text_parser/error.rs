@@ -20,6 +20,8 @@ pub enum FullTextParserError {
     Utf8(#[from] std::str::Utf8Error),
     #[error("Readability Error")]
     Readability,
+    #[error("Document has too many elements ({0})")]
+    TooManyElements(usize),
     #[error("Unknown Error")]
     Unknown,
 }
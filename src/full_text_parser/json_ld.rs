@@ -0,0 +1,241 @@
+use crate::{author::Author, constants, date_parser::DateParser, util::Util};
+use chrono::{DateTime, Utc};
+use libxml::xpath::Context;
+use serde_json::Value;
+
+/// Article metadata published as schema.org JSON-LD
+#[derive(Debug, Clone, Default)]
+pub struct JsonLd {
+    pub headline: Option<String>,
+    pub authors: Vec<Author>,
+    pub date_published: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub image: Option<String>,
+    pub publisher: Option<String>,
+    pub description: Option<String>,
+    pub keywords: Vec<String>,
+    pub section: Option<String>,
+}
+
+impl JsonLd {
+    /// Read the first `<script type="application/ld+json">` describing an article.
+    /// Has to run before `prep_content` strips all scripts.
+    pub fn parse(context: &Context) -> Option<Self> {
+        Util::evaluate_xpath(context, "//script[@type='application/ld+json']", false)
+            .ok()?
+            .iter()
+            .find_map(|script| Self::parse_str(&script.get_content()))
+    }
+
+    pub fn parse_str(json: &str) -> Option<Self> {
+        let json = constants::JSON_LD_CDATA.replace_all(json, "");
+        let value: Value = serde_json::from_str(json.trim())
+            .map_err(|error| log::debug!("Failed to parse JSON-LD: {error}"))
+            .ok()?;
+        let article = Self::find_article(&value)?;
+
+        let headline = Self::string(article, "headline").or_else(|| Self::string(article, "name"));
+        let authors = article.get("author").map(Self::authors).unwrap_or_default();
+        let publisher = article
+            .get("publisher")
+            .and_then(|publisher| Self::names(publisher).into_iter().next());
+        let image = article.get("image").and_then(Self::url);
+
+        Some(Self {
+            headline,
+            authors,
+            date_published: Self::date(article, "datePublished"),
+            date_modified: Self::date(article, "dateModified"),
+            image,
+            publisher,
+            description: Self::string(article, "description"),
+            keywords: article.get("keywords").map(Self::list).unwrap_or_default(),
+            section: article
+                .get("articleSection")
+                .and_then(|section| Self::list(section).into_iter().next()),
+        })
+    }
+
+    fn find_article(value: &Value) -> Option<&Value> {
+        match value {
+            Value::Array(items) => items.iter().find_map(Self::find_article),
+            Value::Object(object) => {
+                if Self::is_article(value) {
+                    Some(value)
+                } else {
+                    object.get("@graph").and_then(Self::find_article)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn is_article(value: &Value) -> bool {
+        match value.get("@type") {
+            Some(Value::String(item_type)) => constants::JSON_LD_ARTICLE_TYPES.is_match(item_type),
+            Some(Value::Array(item_types)) => item_types
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|item_type| constants::JSON_LD_ARTICLE_TYPES.is_match(item_type)),
+            _ => false,
+        }
+    }
+
+    fn string(value: &Value, key: &str) -> Option<String> {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| match escaper::decode_html(s) {
+                Ok(decoded) => decoded,
+                Err(_error) => s.into(),
+            })
+    }
+
+    fn date(value: &Value, key: &str) -> Option<DateTime<Utc>> {
+        let date_string = Self::string(value, key)?;
+        let date = DateParser::parse(&date_string);
+        if date.is_none() {
+            log::warn!("Parsing the date string '{date_string}' failed");
+        }
+        date
+    }
+
+    // `"name"`, `{ "name": "name", "url": "url", "worksFor": organization }` or a list of those
+    fn authors(value: &Value) -> Vec<Author> {
+        match value {
+            Value::String(name) => Author::from_byline(name),
+            Value::Object(_) => {
+                let Some(name) = Self::string(value, "name") else {
+                    return Vec::new();
+                };
+                let organization = value
+                    .get("worksFor")
+                    .or_else(|| value.get("affiliation"))
+                    .and_then(|organization| Self::names(organization).into_iter().next());
+
+                let mut author = Author::new(&name);
+                author.url = Self::string(value, "url");
+                author.organization = organization;
+                vec![author]
+            }
+            Value::Array(items) => items.iter().fold(Vec::new(), |mut authors, item| {
+                for author in Self::authors(item) {
+                    Author::merge(&mut authors, author);
+                }
+                authors
+            }),
+            _ => Vec::new(),
+        }
+    }
+
+    // `"name"`, `{ "name": "name" }` or a list of those
+    fn names(value: &Value) -> Vec<String> {
+        match value {
+            Value::String(name) => vec![name.trim().to_string()],
+            Value::Object(_) => Self::string(value, "name").into_iter().collect(),
+            Value::Array(items) => items.iter().flat_map(Self::names).collect(),
+            _ => Vec::new(),
+        }
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .collect()
+    }
+
+    // `"a, b"` or `["a", "b"]`
+    fn list(value: &Value) -> Vec<String> {
+        match value {
+            Value::String(list) => list
+                .split(',')
+                .map(|item| item.trim().to_string())
+                .collect(),
+            Value::Array(items) => items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|item| item.trim().to_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+        .into_iter()
+        .filter(|item| !item.is_empty())
+        .collect()
+    }
+
+    // `"url"`, `{ "url": "url" }` or a list of those
+    fn url(value: &Value) -> Option<String> {
+        match value {
+            Value::String(url) => Some(url.trim().to_string()).filter(|url| !url.is_empty()),
+            Value::Object(_) => Self::string(value, "url").or_else(|| Self::string(value, "@id")),
+            Value::Array(items) => items.iter().find_map(Self::url),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonLd;
+
+    #[test]
+    fn graph() {
+        let json = r#"{
+            "@context": "https://schema.org",
+            "@graph": [
+                { "@type": "WebSite", "name": "Example" },
+                {
+                    "@type": ["NewsArticle"],
+                    "headline": "Something &amp; Nothing",
+                    "author": [
+                        {
+                            "@type": "Person",
+                            "name": "Jane Doe",
+                            "url": "https://example.com/jane",
+                            "worksFor": { "@type": "Organization", "name": "Example News" }
+                        },
+                        "John Roe"
+                    ],
+                    "datePublished": "2024-03-05T10:00:00+01:00",
+                    "image": { "@type": "ImageObject", "url": "https://example.com/a.jpg" },
+                    "publisher": { "@type": "Organization", "name": "Example News" },
+                    "description": "A summary",
+                    "keywords": "Rust, Parsing",
+                    "articleSection": ["Tech", "Science"]
+                }
+            ]
+        }"#;
+
+        let json_ld = JsonLd::parse_str(json).unwrap();
+        assert_eq!(json_ld.headline.as_deref(), Some("Something & Nothing"));
+        assert_eq!(json_ld.authors.len(), 2);
+        assert_eq!(json_ld.authors[0].name, "Jane Doe");
+        assert_eq!(
+            json_ld.authors[0].url.as_deref(),
+            Some("https://example.com/jane")
+        );
+        assert_eq!(
+            json_ld.authors[0].organization.as_deref(),
+            Some("Example News")
+        );
+        assert_eq!(json_ld.authors[1].name, "John Roe");
+        assert_eq!(
+            json_ld
+                .date_published
+                .map(|date| date.to_rfc3339())
+                .as_deref(),
+            Some("2024-03-05T09:00:00+00:00")
+        );
+        assert!(json_ld.date_modified.is_none());
+        assert_eq!(json_ld.image.as_deref(), Some("https://example.com/a.jpg"));
+        assert_eq!(json_ld.publisher.as_deref(), Some("Example News"));
+        assert_eq!(json_ld.description.as_deref(), Some("A summary"));
+        assert_eq!(json_ld.keywords, vec!["Rust", "Parsing"]);
+        assert_eq!(json_ld.section.as_deref(), Some("Tech"));
+    }
+
+    #[test]
+    fn no_article() {
+        assert!(JsonLd::parse_str(r#"{ "@type": "BreadcrumbList" }"#).is_none());
+        assert!(JsonLd::parse_str("<![CDATA[ not json ]]>").is_none());
+    }
+}
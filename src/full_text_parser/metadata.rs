@@ -1,18 +1,40 @@
 use super::config::ConfigEntry;
-use crate::{article::Article, constants, util::Util};
+use super::json_ld::JsonLd;
+use crate::{article::Article, author::Author, constants, date_parser::DateParser, util::Util};
 use chrono::{DateTime, Utc};
-use libxml::xpath::Context;
+use libxml::{tree::Node, xpath::Context};
 use log::{debug, warn};
-use std::str::FromStr;
+use url::Url;
 
+/// Fill all metadata fields of `article` that are still empty.
+///
+/// Sources are checked in the following order, the first hit wins:
+///
+/// - published date (`Article::date`): site config `date:`, JSON-LD `datePublished`,
+///   global config `date:`, `article:published_time`, `<time pubdate>` or
+///   `<time itemprop="datePublished">`, the first `<time>` that is not labeled as an update
+/// - modified date (`Article::date_modified`): JSON-LD `dateModified`, `article:modified_time`,
+///   `og:updated_time`, `itemprop="dateModified"`, the first `<time>` labeled as an update
+///   (e.g. `<span class="updated"><time>` or `Updated: <time>`)
+///
+/// A modified date older than the published date is discarded.
 pub fn extract(
     context: &Context,
     config: Option<&ConfigEntry>,
     global_config: Option<&ConfigEntry>,
     article: &mut Article,
 ) {
+    let skip_json_ld = config.map(|c| c.skip_json_ld).unwrap_or(false)
+        || global_config.map(|c| c.skip_json_ld).unwrap_or(false);
+    let json_ld = if skip_json_ld {
+        None
+    } else {
+        JsonLd::parse(context)
+    };
+    let json_ld = json_ld.as_ref();
+
     if article.title.is_none() {
-        article.title = extract_title(context, config, global_config)
+        article.title = extract_title(context, config, global_config, json_ld)
             .map(|title| match escaper::decode_html(&title) {
                 Ok(escaped_title) => escaped_title,
                 Err(_error) => title,
@@ -36,18 +58,83 @@ pub fn extract(
             });
     }
 
+    if article.authors.is_empty() {
+        article.authors = extract_authors(context, config, global_config, json_ld);
+
+        for author in &mut article.authors {
+            author.url = author
+                .url
+                .as_deref()
+                .and_then(|url| article.url.join(url).ok())
+                .map(|url| url.to_string());
+        }
+    }
+
     if article.author.is_none() {
-        article.author =
-            extract_author(context, config, global_config).map(
-                |author| match escaper::decode_html(&author) {
-                    Ok(escaped_author) => escaped_author,
-                    Err(_error) => author,
-                },
-            );
+        article.author = join_authors(&article.authors);
     }
 
     if article.date.is_none() {
-        article.date = extract_date(context, config, global_config);
+        article.date = extract_date(context, config, global_config, json_ld);
+    }
+
+    if article.date_modified.is_none() {
+        article.date_modified = extract_date_modified(context, json_ld);
+    }
+
+    if let (Some(published), Some(modified)) = (article.date, article.date_modified) {
+        if modified < published {
+            debug!("Ignoring modified date '{modified}' older than published date '{published}'");
+            article.date_modified = None;
+        }
+    }
+
+    if article.description.is_none() {
+        article.description = extract_description(context, json_ld);
+    }
+
+    if article.site_name.is_none() {
+        article.site_name = extract_site_name(context, json_ld);
+    }
+
+    if article.language.is_none() {
+        article.language = extract_language(context);
+    }
+
+    if article.tags.is_empty() {
+        article.tags = extract_tags(context, json_ld);
+    }
+
+    if article.section.is_none() {
+        article.section = get_meta_property(context, "article:section")
+            .or_else(|| json_ld.and_then(|json_ld| json_ld.section.clone()))
+            .map(|section| normalize_tag(&section))
+            .filter(|section| !section.is_empty());
+    }
+
+    if article.canonical_url.is_none() {
+        article.canonical_url = get_link(context, "canonical")
+            .or_else(|| get_meta_property(context, "og:url"))
+            .and_then(|url| resolve_url(&article.url, &url));
+    }
+
+    if article.amp_url.is_none() {
+        article.amp_url =
+            get_link(context, "amphtml").and_then(|url| resolve_url(&article.url, &url));
+    }
+
+    if article.print_url.is_none() {
+        article.print_url = extract_print_url(context, &article.url);
+    }
+
+    if article.direction.is_none() {
+        article.direction = extract_direction(context, article.language.as_deref());
+    }
+
+    if let Some(json_ld) = json_ld {
+        if article.thumbnail_url.is_none() {
+            article.thumbnail_url = json_ld.image.clone();
+        }
     }
 }
 
@@ -55,6 +142,7 @@ fn extract_title(
     context: &Context,
     config: Option<&ConfigEntry>,
     global_config: Option<&ConfigEntry>,
+    json_ld: Option<&JsonLd>,
 ) -> Option<String> {
     // check site specific config
     if let Some(config) = config {
@@ -66,6 +154,12 @@ fn extract_title(
         }
     }
 
+    // check JSON-LD
+    if let Some(headline) = json_ld.and_then(|json_ld| json_ld.headline.clone()) {
+        debug!("Article title: '{}'", headline);
+        return Some(headline);
+    }
+
     // check global config
     if let Some(global_config) = global_config {
         for xpath_title in &global_config.xpath_title {
@@ -87,49 +181,319 @@ fn extract_title(
         .or_else(|| get_meta(context, "twitter:title"))
 }
 
-fn extract_author(
+/// Use the byline detected by Readability if no other source yielded any authors.
+/// Otherwise it is only used to complete the already known authors.
+pub fn merge_byline(article: &mut Article, byline: &str) {
+    let byline_authors = Author::from_byline(byline);
+    debug!("Article byline: '{}'", byline);
+
+    if article.authors.is_empty() {
+        article.authors = byline_authors;
+    } else {
+        complete_authors(&mut article.authors, byline_authors);
+    }
+
+    if article.author.is_none() {
+        article.author = join_authors(&article.authors);
+    }
+}
+
+fn extract_authors(
     context: &Context,
     config: Option<&ConfigEntry>,
     global_config: Option<&ConfigEntry>,
-) -> Option<String> {
-    // check site specific config
-    if let Some(config) = config {
-        for xpath_author in &config.xpath_author {
-            if let Ok(author) = Util::extract_value(context, xpath_author) {
-                debug!("Article author: '{}'", author);
-                return Some(author);
+    json_ld: Option<&JsonLd>,
+) -> Vec<Author> {
+    // sources in order of priority: the first one with any result wins,
+    // all others only complete URL and organization of those authors
+    let sources = [
+        // check site specific config
+        config
+            .map(|config| config_authors(context, config))
+            .unwrap_or_default(),
+        // check JSON-LD
+        json_ld
+            .map(|json_ld| json_ld.authors.clone())
+            .unwrap_or_default(),
+        // check global config
+        global_config
+            .map(|global_config| config_authors(context, global_config))
+            .unwrap_or_default(),
+        // microdata & links
+        xpath_authors(context, "//*[@itemprop='author'] | //a[@rel='author']"),
+        // generic meta (readablity)
+        Util::extract_value(context, "//author")
+            .ok()
+            .or_else(|| get_meta_property(context, "author"))
+            .or_else(|| get_meta(context, "dc:creator"))
+            .or_else(|| get_meta(context, "dcterm:creator"))
+            .map(|byline| Author::from_byline(&byline))
+            .unwrap_or_default(),
+    ];
+
+    let mut sources = sources.into_iter().filter(|authors| !authors.is_empty());
+    let Some(mut authors) = sources.next() else {
+        return Vec::new();
+    };
+
+    for source in sources {
+        complete_authors(&mut authors, source);
+    }
+
+    for author in &authors {
+        debug!("Article author: '{}'", author.name);
+    }
+
+    authors
+}
+
+fn config_authors(context: &Context, config: &ConfigEntry) -> Vec<Author> {
+    config
+        .xpath_author
+        .iter()
+        .map(|xpath_author| xpath_authors(context, xpath_author))
+        .find(|authors| !authors.is_empty())
+        .unwrap_or_default()
+}
+
+fn xpath_authors(context: &Context, xpath: &str) -> Vec<Author> {
+    let mut authors = Vec::new();
+
+    if let Ok(nodes) = Util::evaluate_xpath(context, xpath, false) {
+        for node in nodes {
+            for author in Author::from_node(&node) {
+                Author::merge(&mut authors, author);
             }
         }
     }
 
-    // check global config
-    if let Some(global_config) = global_config {
-        for xpath_author in &global_config.xpath_author {
-            if let Ok(author) = Util::extract_value(context, xpath_author) {
-                debug!("Article author: '{}'", author);
-                return Some(author);
+    authors
+}
+
+// fill in URL & organization of authors that are already known
+fn complete_authors(authors: &mut Vec<Author>, other: Vec<Author>) {
+    for author in other {
+        let is_known = authors
+            .iter()
+            .any(|known| known.name.to_lowercase() == author.name.to_lowercase());
+        if is_known {
+            Author::merge(authors, author);
+        }
+    }
+}
+
+fn join_authors(authors: &[Author]) -> Option<String> {
+    if authors.is_empty() {
+        None
+    } else {
+        Some(
+            authors
+                .iter()
+                .map(|author| author.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+}
+
+fn extract_description(context: &Context, json_ld: Option<&JsonLd>) -> Option<String> {
+    get_meta_property(context, "og:description")
+        .or_else(|| get_meta_property(context, "description"))
+        .or_else(|| get_meta_property(context, "twitter:description"))
+        .or_else(|| get_meta(context, "dc:description"))
+        .or_else(|| get_meta(context, "dcterm:description"))
+        .or_else(|| json_ld.and_then(|json_ld| json_ld.description.clone()))
+        .map(|description| match escaper::decode_html(&description) {
+            Ok(decoded) => decoded,
+            Err(_error) => description,
+        })
+        .map(|description| {
+            constants::NORMALIZE
+                .replace_all(description.trim(), " ")
+                .into()
+        })
+        .filter(|description: &String| !description.is_empty())
+}
+
+fn extract_site_name(context: &Context, json_ld: Option<&JsonLd>) -> Option<String> {
+    get_meta_property(context, "og:site_name")
+        .or_else(|| json_ld.and_then(|json_ld| json_ld.publisher.clone()))
+        .or_else(|| get_meta_property(context, "application-name"))
+        .map(|site_name| match escaper::decode_html(&site_name) {
+            Ok(decoded) => decoded,
+            Err(_error) => site_name,
+        })
+        .map(|site_name| site_name.trim().to_string())
+        .filter(|site_name| !site_name.is_empty())
+}
+
+fn extract_language(context: &Context) -> Option<String> {
+    Util::get_attribute(context, "//html", "lang")
+        .ok()
+        .or_else(|| Util::get_attribute(context, "//html", "xml:lang").ok())
+        .or_else(|| {
+            Util::get_attribute(
+                context,
+                "//meta[translate(@http-equiv, 'CONTENT-LANGUAGE', 'content-language')='content-language']",
+                "content",
+            )
+            .ok()
+        })
+        .or_else(|| get_meta_property(context, "og:locale"))
+        .and_then(|language| {
+            // `de, en` -> `de`, `en_US` -> `en-US`
+            language
+                .split(',')
+                .next()
+                .map(|language| language.trim().replace('_', "-"))
+        })
+        .filter(|language| constants::LANGUAGE_TAG.is_match(language))
+}
+
+fn extract_direction(context: &Context, language: Option<&str>) -> Option<String> {
+    Util::get_attribute(context, "//html", "dir")
+        .ok()
+        .or_else(|| Util::get_attribute(context, "//body", "dir").ok())
+        .map(|direction| direction.trim().to_lowercase())
+        .filter(|direction| direction == "ltr" || direction == "rtl" || direction == "auto")
+        .or_else(|| {
+            let language = language?.split('-').next()?.to_lowercase();
+            if constants::RTL_LANGUAGES.contains(&language.as_str()) {
+                Some("rtl".into())
+            } else {
+                None
             }
+        })
+}
+
+fn extract_tags(context: &Context, json_ld: Option<&JsonLd>) -> Vec<String> {
+    let mut tags = Vec::new();
+
+    let meta_tags = Util::evaluate_xpath(
+        context,
+        "//meta[@property='article:tag' or @name='article:tag']",
+        false,
+    )
+    .unwrap_or_default()
+    .iter()
+    .filter_map(|meta| meta.get_attribute("content"))
+    .collect::<Vec<_>>();
+
+    let keywords = ["news_keywords", "keywords"]
+        .iter()
+        .filter_map(|name| get_meta_property(context, name))
+        .flat_map(|keywords| keywords.split(',').map(String::from).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let links = Util::evaluate_xpath(context, "//a[@rel='tag']", false)
+        .unwrap_or_default()
+        .iter()
+        .map(|link| link.get_content())
+        .collect::<Vec<_>>();
+
+    let json_ld_keywords = json_ld
+        .map(|json_ld| json_ld.keywords.clone())
+        .unwrap_or_default();
+
+    for tag in meta_tags
+        .into_iter()
+        .chain(keywords)
+        .chain(json_ld_keywords)
+        .chain(links)
+    {
+        let tag = normalize_tag(&tag);
+        if tag.is_empty() || tag.chars().count() > constants::TAG_MAX_LENGTH {
+            continue;
+        }
+
+        let key = tag.to_lowercase();
+        if !tags
+            .iter()
+            .any(|known: &String| known.to_lowercase() == key)
+        {
+            tags.push(tag);
         }
     }
 
-    // generic meta (readablity)
-    Util::extract_value(context, "//author")
+    tags
+}
+
+fn normalize_tag(tag: &str) -> String {
+    let tag = match escaper::decode_html(tag) {
+        Ok(decoded) => decoded,
+        Err(_error) => tag.into(),
+    };
+    constants::NORMALIZE
+        .replace_all(tag.trim().trim_start_matches('#'), " ")
+        .trim()
+        .to_string()
+}
+
+fn extract_print_url(context: &Context, base_url: &Url) -> Option<Url> {
+    if let Some(url) = Util::get_attribute(
+        context,
+        "//link[@rel='alternate' and @media='print']",
+        "href",
+    )
+    .ok()
+    .and_then(|url| resolve_url(base_url, &url))
+    {
+        return Some(url);
+    }
+
+    // links to a print version on the same host
+    Util::evaluate_xpath(context, "//a[@href]", false)
+        .ok()?
+        .iter()
+        .filter(|link| {
+            constants::PRINT_LINK.is_match(Util::get_signature(link).trim())
+                || constants::PRINT_LINK.is_match(link.get_content().trim())
+        })
+        .filter_map(|link| link.get_attribute("href"))
+        .filter_map(|href| resolve_url(base_url, &href))
+        .find(|url| url.host() == base_url.host() && url != base_url)
+}
+
+fn get_link(context: &Context, rel: &str) -> Option<String> {
+    Util::get_attribute(context, &format!("//link[@rel='{rel}']"), "href").ok()
+}
+
+// absolute http(s) URL of `url` relative to the page
+fn resolve_url(base_url: &Url, url: &str) -> Option<Url> {
+    base_url
+        .join(url.trim())
         .ok()
-        .or_else(|| get_meta(context, "dc:creator"))
-        .or_else(|| get_meta(context, "dcterm:creator"))
+        .filter(|url| url.scheme() == "http" || url.scheme() == "https")
+}
+
+/// Fallback description: text of the first paragraph in the extracted article
+/// that is long enough to be a summary (or the first non-empty one).
+pub fn extract_excerpt(root: &Node) -> Option<String> {
+    let paragraphs = Util::get_elements_by_tag_name(root, "p")
+        .iter()
+        .map(|paragraph| Util::get_inner_text(paragraph, true))
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>();
+
+    paragraphs
+        .iter()
+        .find(|text| text.chars().count() >= constants::EXCERPT_MIN_LENGTH)
+        .or_else(|| paragraphs.first())
+        .cloned()
 }
 
 fn extract_date(
     context: &Context,
     config: Option<&ConfigEntry>,
     global_config: Option<&ConfigEntry>,
+    json_ld: Option<&JsonLd>,
 ) -> Option<DateTime<Utc>> {
     // check site specific config
     if let Some(config) = config {
         for xpath_date in &config.xpath_date {
             if let Ok(date_string) = Util::extract_value(context, xpath_date) {
                 debug!("Article date: '{}'", date_string);
-                if let Ok(date) = DateTime::from_str(&date_string) {
+                if let Some(date) = DateParser::parse(&date_string) {
                     return Some(date);
                 } else {
                     warn!("Parsing the date string '{}' failed", date_string);
@@ -138,12 +502,18 @@ fn extract_date(
         }
     }
 
+    // check JSON-LD
+    if let Some(date) = json_ld.and_then(|json_ld| json_ld.date_published) {
+        debug!("Article date: '{}'", date);
+        return Some(date);
+    }
+
     // check global config
     if let Some(global_config) = global_config {
         for xpath_date in &global_config.xpath_date {
             if let Ok(date_string) = Util::extract_value(context, xpath_date) {
                 debug!("Article date: '{}'", date_string);
-                if let Ok(date) = DateTime::from_str(&date_string) {
+                if let Some(date) = DateParser::parse(&date_string) {
                     return Some(date);
                 } else {
                     warn!("Parsing the date string '{}' failed", date_string);
@@ -152,7 +522,97 @@ fn extract_date(
         }
     }
 
-    None
+    // generic meta & <time> elements
+    get_meta_property(context, "article:published_time")
+        .or_else(|| {
+            Util::get_attribute(
+                context,
+                "//time[@pubdate or @itemprop='datePublished']",
+                "datetime",
+            )
+            .ok()
+        })
+        .or_else(|| find_time(context, false))
+        .and_then(|date_string| {
+            debug!("Article date: '{}'", date_string);
+            parse_date(&date_string)
+        })
+}
+
+fn extract_date_modified(context: &Context, json_ld: Option<&JsonLd>) -> Option<DateTime<Utc>> {
+    // check JSON-LD
+    if let Some(date) = json_ld.and_then(|json_ld| json_ld.date_modified) {
+        debug!("Article modified date: '{}'", date);
+        return Some(date);
+    }
+
+    // generic meta & <time> elements
+    get_meta_property(context, "article:modified_time")
+        .or_else(|| get_meta_property(context, "og:updated_time"))
+        .or_else(|| {
+            Util::get_attribute(context, "//meta[@itemprop='dateModified']", "content").ok()
+        })
+        .or_else(|| {
+            Util::get_attribute(context, "//time[@itemprop='dateModified']", "datetime").ok()
+        })
+        .or_else(|| find_time(context, true))
+        .and_then(|date_string| {
+            debug!("Article modified date: '{}'", date_string);
+            parse_date(&date_string)
+        })
+}
+
+fn parse_date(date_string: &str) -> Option<DateTime<Utc>> {
+    let date = DateParser::parse(date_string);
+    if date.is_none() {
+        warn!("Parsing the date string '{}' failed", date_string);
+    }
+    date
+}
+
+// first `<time>` element that is (`updated == true`) or isn't labeled as the last update
+fn find_time(context: &Context, updated: bool) -> Option<String> {
+    let times = Util::evaluate_xpath(context, "//time", false).ok()?;
+    let times = times
+        .iter()
+        .filter(|time| is_update_time(time) == updated)
+        .collect::<Vec<_>>();
+
+    times
+        .iter()
+        .find_map(|time| time.get_attribute("datetime"))
+        .or_else(|| times.iter().map(|time| time.get_content()).next())
+        .map(|date_string| date_string.trim().to_string())
+        .filter(|date_string| !date_string.is_empty())
+}
+
+fn is_update_time(time: &Node) -> bool {
+    if time.get_attribute("itemprop").as_deref() == Some("dateModified") {
+        return true;
+    }
+
+    let parent_signature = time
+        .get_parent()
+        .map(|parent| Util::get_signature(&parent))
+        .unwrap_or_default();
+    let previous_text = time
+        .get_prev_sibling()
+        .filter(|sibling| sibling.is_text_node())
+        .map(|sibling| sibling.get_content())
+        .unwrap_or_default();
+
+    constants::DATE_UPDATED.is_match(&Util::get_signature(time))
+        || constants::DATE_UPDATED.is_match(&parent_signature)
+        || constants::DATE_UPDATED.is_match(&previous_text)
+}
+
+fn get_meta_property(context: &Context, property: &str) -> Option<String> {
+    Util::get_attribute(
+        context,
+        &format!("//meta[@property='{property}' or @name='{property}']"),
+        "content",
+    )
+    .ok()
 }
 
 fn get_meta(context: &Context, name: &str) -> Option<String> {
@@ -163,3 +623,185 @@ fn get_meta(context: &Context, name: &str) -> Option<String> {
     )
     .ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{article::Article, FullTextParser};
+    use url::Url;
+
+    #[test]
+    fn published_and_modified() {
+        let html = r#"<html><head>
+            <meta property="og:updated_time" content="2024-03-06T08:00:00Z">
+        </head><body>
+            <p class="byline">Posted <time datetime="2024-03-05T10:00:00Z">March 5</time></p>
+            <p>Updated: <time datetime="2024-03-07T12:30:00Z">March 7</time></p>
+        </body></html>"#;
+
+        let document = FullTextParser::parse_html_string_patched(html).unwrap();
+        let context = FullTextParser::get_xpath_ctx(&document).unwrap();
+        let mut article = Article::new(Url::parse("https://example.com").unwrap());
+        super::extract(&context, None, None, &mut article);
+
+        assert_eq!(
+            article.date.map(|date| date.to_rfc3339()).as_deref(),
+            Some("2024-03-05T10:00:00+00:00")
+        );
+        assert_eq!(
+            article
+                .date_modified
+                .map(|date| date.to_rfc3339())
+                .as_deref(),
+            Some("2024-03-06T08:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn updated_time_element() {
+        let html = r#"<html><body>
+            <span class="date-updated"><time datetime="2024-03-07">7.3.</time></span>
+            <time datetime="2024-03-08T00:00:00Z">8.3.</time>
+            <span>Last modified <time datetime="2024-03-09">9.3.</time></span>
+        </body></html>"#;
+
+        let document = FullTextParser::parse_html_string_patched(html).unwrap();
+        let context = FullTextParser::get_xpath_ctx(&document).unwrap();
+        let mut article = Article::new(Url::parse("https://example.com").unwrap());
+        super::extract(&context, None, None, &mut article);
+
+        assert_eq!(
+            article.date.map(|date| date.to_rfc3339()).as_deref(),
+            Some("2024-03-08T00:00:00+00:00")
+        );
+        // the first update is older than the published date and therefore dropped
+        assert!(article.date_modified.is_none());
+    }
+
+    #[test]
+    fn authors() {
+        let html = r#"<html><head>
+            <meta name="author" content="Jane Doe">
+        </head><body>
+            <p class="byline">By <a rel="author" href="/jane">Jane Doe</a>
+                and <span itemprop="author" itemscope><span itemprop="name">John Roe</span></span></p>
+        </body></html>"#;
+
+        let document = FullTextParser::parse_html_string_patched(html).unwrap();
+        let context = FullTextParser::get_xpath_ctx(&document).unwrap();
+        let mut article = Article::new(Url::parse("https://example.com").unwrap());
+        super::extract(&context, None, None, &mut article);
+
+        assert_eq!(article.authors.len(), 2);
+        assert_eq!(article.authors[0].name, "Jane Doe");
+        assert_eq!(
+            article.authors[0].url.as_deref(),
+            Some("https://example.com/jane")
+        );
+        assert_eq!(article.authors[1].name, "John Roe");
+        assert_eq!(article.author.as_deref(), Some("Jane Doe, John Roe"));
+
+        // the byline only completes the known authors
+        super::merge_byline(&mut article, "By Jane Doe, John Roe and Max Mustermann");
+        assert_eq!(article.authors.len(), 2);
+    }
+
+    #[test]
+    fn description() {
+        let html = r#"<html><head>
+            <meta name="twitter:description" content="Twitter">
+            <meta property="og:description" content=" Tom &amp;   Jerry ">
+        </head><body></body></html>"#;
+
+        let document = FullTextParser::parse_html_string_patched(html).unwrap();
+        let context = FullTextParser::get_xpath_ctx(&document).unwrap();
+        let mut article = Article::new(Url::parse("https://example.com").unwrap());
+        super::extract(&context, None, None, &mut article);
+
+        assert_eq!(article.description.as_deref(), Some("Tom & Jerry"));
+    }
+
+    #[test]
+    fn excerpt() {
+        let html = r#"<article>
+            <p>Short teaser</p>
+            <p>This is the first paragraph of the article that is long enough to summarize it properly.</p>
+        </article>"#;
+
+        let document = FullTextParser::parse_html_string_patched(html).unwrap();
+        let root = document.get_root_element().unwrap();
+
+        assert_eq!(
+            super::extract_excerpt(&root).as_deref(),
+            Some("This is the first paragraph of the article that is long enough to summarize it properly.")
+        );
+    }
+
+    #[test]
+    fn site_name_language_direction() {
+        let html = r#"<html lang="ar_EG"><head>
+            <meta property="og:site_name" content="Example News">
+        </head><body></body></html>"#;
+
+        let document = FullTextParser::parse_html_string_patched(html).unwrap();
+        let context = FullTextParser::get_xpath_ctx(&document).unwrap();
+        let mut article = Article::new(Url::parse("https://example.com").unwrap());
+        super::extract(&context, None, None, &mut article);
+
+        assert_eq!(article.site_name.as_deref(), Some("Example News"));
+        assert_eq!(article.language.as_deref(), Some("ar-EG"));
+        assert_eq!(article.direction.as_deref(), Some("rtl"));
+    }
+
+    #[test]
+    fn links() {
+        let html = r#"<html><head>
+            <link rel="canonical" href="/news/story">
+            <link rel="amphtml" href="https://amp.example.com/news/story">
+        </head><body>
+            <a href="javascript:window.print()">Print</a>
+            <a class="print-link" href="/news/story?print=1">Drucken</a>
+        </body></html>"#;
+
+        let document = FullTextParser::parse_html_string_patched(html).unwrap();
+        let context = FullTextParser::get_xpath_ctx(&document).unwrap();
+        let url = Url::parse("https://example.com/news/story?utm_source=feed").unwrap();
+        let mut article = Article::new(url);
+        super::extract(&context, None, None, &mut article);
+
+        assert_eq!(
+            article.canonical_url.as_ref().map(Url::as_str),
+            Some("https://example.com/news/story")
+        );
+        assert_eq!(
+            article.amp_url.as_ref().map(Url::as_str),
+            Some("https://amp.example.com/news/story")
+        );
+        assert_eq!(
+            article.print_url.as_ref().map(Url::as_str),
+            Some("https://example.com/news/story?print=1")
+        );
+    }
+
+    #[test]
+    fn tags_and_section() {
+        let html = r#"<html><head>
+            <meta property="article:section" content=" Technology ">
+            <meta property="article:tag" content="Rust">
+            <meta property="article:tag" content="Web  Scraping">
+            <meta name="keywords" content="rust, HTML,, parsing">
+        </head><body>
+            <a rel="tag" href="/tag/html">#html</a>
+        </body></html>"#;
+
+        let document = FullTextParser::parse_html_string_patched(html).unwrap();
+        let context = FullTextParser::get_xpath_ctx(&document).unwrap();
+        let mut article = Article::new(Url::parse("https://example.com").unwrap());
+        super::extract(&context, None, None, &mut article);
+
+        assert_eq!(
+            article.tags,
+            vec!["Rust", "Web Scraping", "HTML", "parsing"]
+        );
+        assert_eq!(article.section.as_deref(), Some("Technology"));
+    }
+}
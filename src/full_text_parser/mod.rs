@@ -1,13 +1,19 @@
 pub mod config;
 pub mod error;
+mod json_ld;
 mod metadata;
 mod readability;
 
-use self::config::{ConfigCollection, ConfigEntry};
+use self::config::{ConfigCollection, ConfigEntry, XPathMatch};
 use self::error::FullTextParserError;
-pub use self::readability::Readability;
+pub use self::readability::{
+    Readability, ReadabilityOptions, ReadabilityResult, ReadabilityTrace, ReaderableOptions,
+    TraceAttempt, TraceCandidate, TraceOptions,
+};
 use crate::article::Article;
 use crate::constants;
+use crate::footnote::Footnote;
+use crate::statistics::Statistics;
 use crate::util::Util;
 
 use libxml::parser::Parser;
@@ -46,14 +52,7 @@ impl FullTextParser {
             .get("global.txt")
             .ok_or(FullTextParserError::Config)?;
 
-        let mut article = Article {
-            title: None,
-            author: None,
-            url: url.clone(),
-            date: None,
-            thumbnail_url: None,
-            html: None,
-        };
+        let mut article = Article::new(url.clone());
 
         libxml::tree::node::set_node_rc_guard(10);
 
@@ -62,8 +61,21 @@ impl FullTextParser {
             Node::new("article", None, &document).map_err(|()| FullTextParserError::Xml)?;
         document.set_root_element(&root);
 
+        let mut endnotes = None;
         for page_html in pages {
-            self.parse_page(&mut article, &page_html, &mut root, config, global_config)?;
+            self.parse_page(
+                &mut article,
+                &page_html,
+                &mut root,
+                &mut endnotes,
+                config,
+                global_config,
+            )?;
+        }
+
+        // a single endnotes section for all pages, behind the content of the last one
+        if let Some(section) = endnotes {
+            Self::move_to_end(&mut root, section)?;
         }
 
         let context = Context::new(&document).map_err(|()| {
@@ -76,17 +88,64 @@ impl FullTextParser {
             return Err(error);
         }
 
-        Self::post_process_document(&document)?;
+        Self::post_process_document(&document, &ReadabilityOptions::default())?;
+
+        if article.description.is_none() {
+            article.description = metadata::extract_excerpt(&root);
+        }
+
+        if let Some(direction) = article.direction.as_deref() {
+            _ = root.set_attribute("dir", direction);
+        }
+
+        article.statistics = Some(Statistics::from_node(&root));
+
         article.html = Some(Util::serialize_node(&document, &root));
 
         Ok(article)
     }
 
+    /// Count the nodes the `title`, `body`, `date` & `author` XPaths of `config` select in `html`.
+    /// Useful to find out which XPaths of a site config stopped matching.
+    pub fn match_config_xpaths(
+        &self,
+        config: &ConfigEntry,
+        html: &str,
+    ) -> Result<Vec<XPathMatch>, FullTextParserError> {
+        let global_config = self
+            .config_files
+            .get("global.txt")
+            .ok_or(FullTextParserError::Config)?;
+
+        let document = Self::parse_html(html, Some(config), global_config)?;
+        let xpath_ctx = Self::get_xpath_ctx(&document)?;
+
+        let directives = [
+            ("title", &config.xpath_title),
+            ("body", &config.xpath_body),
+            ("date", &config.xpath_date),
+            ("author", &config.xpath_author),
+        ];
+
+        Ok(directives
+            .into_iter()
+            .flat_map(|(directive, xpaths)| xpaths.iter().map(move |xpath| (directive, xpath)))
+            .map(|(directive, xpath)| XPathMatch {
+                directive: directive.into(),
+                xpath: xpath.clone(),
+                count: Util::evaluate_xpath(&xpath_ctx, xpath, false)
+                    .map(|nodes| nodes.len())
+                    .unwrap_or(0),
+            })
+            .collect())
+    }
+
     fn parse_page(
         &self,
         article: &mut Article,
         html: &str,
         root: &mut Node,
+        endnotes: &mut Option<Node>,
         config: Option<&ConfigEntry>,
         global_config: &ConfigEntry,
     ) -> Result<(), FullTextParserError> {
@@ -98,6 +157,18 @@ impl FullTextParser {
         if article.thumbnail_url.is_none() {
             article.thumbnail_url = Self::check_for_thumbnail(&xpath_ctx);
         }
+
+        // footnotes have to be collected before stripping footers & asides
+        let (footnotes, footnote_section) = Footnote::extract(
+            &xpath_ctx,
+            &document,
+            root,
+            config,
+            Some(global_config),
+            &article.url,
+        )?;
+        article.footnotes.extend(footnotes);
+
         Self::prep_content(
             &xpath_ctx,
             config,
@@ -109,16 +180,31 @@ impl FullTextParser {
         let found_body = Self::extract_body(&xpath_ctx, root, config, global_config)?;
 
         if !found_body {
-            if let Err(error) = Readability::extract_body(document, root, article.title.as_deref())
+            if let Err(error) =
+                Readability::extract_body(document, root, article, &ReadabilityOptions::default())
             {
                 log::error!("Both ftr and readability failed to find content: {error}");
                 return Err(error);
             }
         }
 
+        match (endnotes.as_ref(), footnote_section) {
+            (Some(endnotes), Some(section)) => Footnote::merge_sections(endnotes, section)?,
+            (None, Some(section)) => *endnotes = Some(section),
+            (_, None) => {}
+        }
+
         Ok(())
     }
 
+    pub(crate) fn move_to_end(root: &mut Node, mut node: Node) -> Result<(), FullTextParserError> {
+        node.unlink();
+        root.add_child(&mut node).map_err(|error| {
+            log::error!("{error}");
+            FullTextParserError::Xml
+        })
+    }
+
     pub(crate) fn parse_html(
         html: &str,
         config: Option<&ConfigEntry>,
@@ -149,7 +235,7 @@ impl FullTextParser {
     /// See:
     /// - <https://github.com/KWARC/rust-libxml/issues/111>
     /// - <https://github.com/Orange-OpenSource/hurl/issues/1535>
-    ///     These two functions should be removed when the issue is fixed in libxml crate.
+    ///   These two functions should be removed when the issue is fixed in libxml crate.
     fn try_usize_to_i32(value: usize) -> Result<i32, libxml::parser::XmlParseError> {
         if cfg!(target_pointer_width = "16") || (value < i32::MAX as usize) {
             // Cannot safely use our value comparison, but the conversion if always safe.
@@ -246,6 +332,12 @@ impl FullTextParser {
         conf
     }
 
+    /// Decode raw HTML read from a file or stream using the charset of `content_type`,
+    /// a byte order mark or a `<meta charset>` tag. Falls back to UTF-8.
+    pub fn decode_html(data: &[u8], content_type: Option<&str>) -> String {
+        Util::decode_html(data, content_type)
+    }
+
     pub fn thumbnail_from_html(html: &str) -> Option<String> {
         if let Ok(doc) = Self::parse_html_string_patched(html) {
             if let Ok(ctx) = Self::get_xpath_ctx(&doc) {
@@ -881,7 +973,7 @@ impl FullTextParser {
                     return Err(FullTextParserError::Xml);
                 }
 
-                Self::post_process_page(&mut node)?;
+                Self::post_process_page(&mut node, &ReadabilityOptions::default())?;
 
                 node.unlink();
                 if root.add_child(&mut node).is_ok() {
@@ -914,10 +1006,13 @@ impl FullTextParser {
         Ok(())
     }
 
-    pub(crate) fn post_process_document(document: &Document) -> Result<(), FullTextParserError> {
+    pub(crate) fn post_process_document(
+        document: &Document,
+        options: &ReadabilityOptions,
+    ) -> Result<(), FullTextParserError> {
         if let Some(mut root) = document.get_root_element() {
             Self::simplify_nested_elements(&mut root)?;
-            Self::clean_attributes(&mut root)?;
+            Self::clean_attributes(&mut root, options)?;
             Self::remove_single_cell_tables(&mut root);
             Self::remove_extra_p_and_div(&mut root);
         }
@@ -925,16 +1020,19 @@ impl FullTextParser {
         Ok(())
     }
 
-    pub(crate) fn post_process_page(node: &mut Node) -> Result<(), FullTextParserError> {
+    pub(crate) fn post_process_page(
+        node: &mut Node,
+        options: &ReadabilityOptions,
+    ) -> Result<(), FullTextParserError> {
         Util::clean_headers(node);
         Util::replace_schema_org_orbjects(node);
-        Util::clean_conditionally(node, "fieldset");
-        Util::clean_conditionally(node, "table");
-        Util::clean_conditionally(node, "ul");
-        Util::clean_conditionally(node, "div");
+        Util::clean_conditionally(node, "fieldset", options.link_density_modifier);
+        Util::clean_conditionally(node, "table", options.link_density_modifier);
+        Util::clean_conditionally(node, "ul", options.link_density_modifier);
+        Util::clean_conditionally(node, "div", options.link_density_modifier);
 
-        Self::remove_share_elements(node);
-        Self::clean_attributes(node)?;
+        Self::remove_share_elements(node, options.char_threshold);
+        Self::clean_attributes(node, options)?;
         Self::remove_single_cell_tables(node);
         Self::remove_extra_p_and_div(node);
         Self::remove_empty_nodes(node);
@@ -1002,7 +1100,27 @@ impl FullTextParser {
         }
     }
 
-    fn remove_share_elements(root: &mut Node) {
+    fn clean_classes(node: &mut Node, preserve: &[String]) -> Result<(), FullTextParserError> {
+        let classes = node
+            .get_attribute("class")
+            .unwrap_or_default()
+            .split_whitespace()
+            .filter(|class| preserve.iter().any(|preserved| preserved == class))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if classes.is_empty() {
+            node.remove_attribute("class")
+        } else {
+            node.set_attribute("class", &classes)
+        }
+        .map_err(|e| {
+            log::error!("{e}");
+            FullTextParserError::Xml
+        })
+    }
+
+    fn remove_share_elements(root: &mut Node, char_threshold: usize) {
         let mut node_iter = Some(root.clone());
 
         while let Some(mut node) = node_iter {
@@ -1013,7 +1131,7 @@ impl FullTextParser {
             );
 
             if constants::SHARE_ELEMENTS.is_match(&match_string)
-                && node.get_content().len() < constants::DEFAULT_CHAR_THRESHOLD
+                && node.get_content().chars().count() < char_threshold
             {
                 node_iter = Util::remove_and_next(&mut node);
             } else {
@@ -1022,7 +1140,10 @@ impl FullTextParser {
         }
     }
 
-    fn clean_attributes(root: &mut Node) -> Result<(), FullTextParserError> {
+    fn clean_attributes(
+        root: &mut Node,
+        options: &ReadabilityOptions,
+    ) -> Result<(), FullTextParserError> {
         let mut node_iter = Some(root.clone());
 
         while let Some(mut node) = node_iter {
@@ -1037,10 +1158,9 @@ impl FullTextParser {
                 _ = node.remove_attribute("height");
             }
 
-            node.remove_attribute("class").map_err(|e| {
-                log::error!("{e}");
-                FullTextParserError::Xml
-            })?;
+            if !options.keep_classes {
+                Self::clean_classes(&mut node, &options.classes_to_preserve)?;
+            }
 
             node.remove_attribute("align").map_err(|e| {
                 log::error!("{e}");
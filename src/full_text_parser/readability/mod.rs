@@ -1,11 +1,18 @@
+mod options;
+mod result;
 mod state;
+mod trace;
 
 #[cfg(test)]
 mod tests;
 
+pub use self::options::{ReadabilityOptions, ReaderableOptions, TraceOptions};
+pub use self::result::ReadabilityResult;
 use self::state::State;
-use super::error::FullTextParserError;
-use crate::{constants, util::Util};
+use self::trace::TraceRecorder;
+pub use self::trace::{ReadabilityTrace, TraceAttempt, TraceCandidate};
+use super::{error::FullTextParserError, metadata};
+use crate::{article::Article, constants, statistics::Statistics, util::Util};
 use libxml::tree::{Document, Node};
 use std::cmp::Ordering;
 
@@ -23,45 +30,254 @@ impl Readability {
     /// * `base_url` - URL used to complete relative URLs
     ///
     pub fn extract(html: &str, base_url: Option<url::Url>) -> Result<String, FullTextParserError> {
+        let article = Self::extract_article(html, base_url)?;
+        article.html.ok_or(FullTextParserError::Readability)
+    }
+
+    /// Like [`Readability::extract`] but returns the complete [`Article`] including metadata
+    pub fn extract_article(
+        html: &str,
+        base_url: Option<url::Url>,
+    ) -> Result<Article, FullTextParserError> {
+        Self::extract_article_with(html, base_url, &ReadabilityOptions::default())
+    }
+
+    /// Like [`Readability::extract_article`] with custom [`ReadabilityOptions`]
+    pub fn extract_article_with(
+        html: &str,
+        base_url: Option<url::Url>,
+        options: &ReadabilityOptions,
+    ) -> Result<Article, FullTextParserError> {
+        Self::extract_article_and_text(html, base_url, options, &mut State::default())
+            .map(|(article, _text)| article)
+    }
+
+    /// Counterpart of Mozilla's `Readability.parse()`: the content together with its
+    /// metadata and plain text
+    pub fn parse(
+        html: &str,
+        base_url: Option<url::Url>,
+        options: &ReadabilityOptions,
+    ) -> Result<ReadabilityResult, FullTextParserError> {
+        Self::parse_with_state(html, base_url, options, &mut State::default())
+    }
+
+    /// Like [`Readability::parse`] but also records how the content was scored & selected
+    pub fn parse_with_trace(
+        html: &str,
+        base_url: Option<url::Url>,
+        options: &ReadabilityOptions,
+        trace_options: &TraceOptions,
+    ) -> Result<(ReadabilityResult, ReadabilityTrace), FullTextParserError> {
+        let mut state = State {
+            trace: Some(TraceRecorder::new(trace_options)),
+            ..Default::default()
+        };
+        let result = Self::parse_with_state(html, base_url, options, &mut state)?;
+        let trace = state
+            .trace
+            .map(|recorder| recorder.trace)
+            .unwrap_or_default();
+        Ok((result, trace))
+    }
+
+    fn parse_with_state(
+        html: &str,
+        base_url: Option<url::Url>,
+        options: &ReadabilityOptions,
+        state: &mut State,
+    ) -> Result<ReadabilityResult, FullTextParserError> {
+        let (article, text_content) =
+            Self::extract_article_and_text(html, base_url, options, state)?;
+        let content = article.html.ok_or(FullTextParserError::Readability)?;
+
+        Ok(ReadabilityResult {
+            title: article.title,
+            byline: article.author,
+            dir: article.direction,
+            lang: article.language,
+            content,
+            length: text_content.chars().count(),
+            text_content,
+            excerpt: article.description,
+            site_name: article.site_name,
+            published_time: article.date,
+        })
+    }
+
+    fn extract_article_and_text(
+        html: &str,
+        base_url: Option<url::Url>,
+        options: &ReadabilityOptions,
+        state: &mut State,
+    ) -> Result<(Article, String), FullTextParserError> {
         libxml::tree::node::set_node_rc_guard(10);
-        let empty_config = crate::full_text_parser::config::ConfigEntry::default();
+        let empty_config = crate::full_text_parser::config::ConfigEntry {
+            skip_json_ld: options.disable_json_ld,
+            ..Default::default()
+        };
 
         let url =
             base_url.unwrap_or_else(|| url::Url::parse("http://fakehost/test/base/").unwrap());
         let document = crate::FullTextParser::parse_html(html, None, &empty_config)?;
+
+        if let Some(max_elems) = options.max_elems_to_parse {
+            let count = document
+                .get_root_element()
+                .map(|root| Util::get_elements_by_tag_name(&root, "*").len())
+                .unwrap_or(0);
+            if count > max_elems {
+                log::error!("Aborting parsing document; {count} elements found");
+                return Err(FullTextParserError::TooManyElements(count));
+            }
+        }
+
         let xpath_ctx = crate::FullTextParser::get_xpath_ctx(&document)?;
 
-        crate::FullTextParser::prep_content(&xpath_ctx, None, &empty_config, &url, &document, None);
-        let mut article = crate::article::Article {
-            title: None,
-            author: None,
-            url,
-            date: None,
-            thumbnail_url: None,
-            html: None,
-        };
+        let mut article = crate::article::Article::new(url);
 
         let mut article_document = Document::new().map_err(|()| FullTextParserError::Xml)?;
         let mut root =
-            Node::new("article", None, &document).map_err(|()| FullTextParserError::Xml)?;
+            Node::new("article", None, &article_document).map_err(|()| FullTextParserError::Xml)?;
         article_document.set_root_element(&root);
 
-        crate::full_text_parser::metadata::extract(&xpath_ctx, None, None, &mut article);
-        super::Readability::extract_body(document, &mut root, article.title.as_deref())?;
-        crate::FullTextParser::post_process_document(&article_document)?;
+        let (footnotes, footnote_section) = crate::footnote::Footnote::extract(
+            &xpath_ctx,
+            &document,
+            &mut root,
+            None,
+            None,
+            &article.url,
+        )?;
+        article.footnotes = footnotes;
+
+        // metadata has to be read before `prep_content` removes JSON-LD scripts
+        crate::full_text_parser::metadata::extract(
+            &xpath_ctx,
+            Some(&empty_config),
+            None,
+            &mut article,
+        );
 
-        let html = Util::serialize_node(&article_document, &root);
-        article.html.replace(html.clone());
+        crate::FullTextParser::prep_content(
+            &xpath_ctx,
+            None,
+            &empty_config,
+            &article.url,
+            &document,
+            None,
+        );
+        Self::extract_body_with_state(document, &mut root, &mut article, options, state)?;
+
+        if let Some(section) = footnote_section {
+            crate::FullTextParser::move_to_end(&mut root, section)?;
+        }
+
+        crate::FullTextParser::post_process_document(&article_document, options)?;
+
+        if article.description.is_none() {
+            article.description = metadata::extract_excerpt(&root);
+        }
+
+        if let Some(direction) = article.direction.as_deref() {
+            _ = root.set_attribute("dir", direction);
+        }
+
+        article.statistics = Some(Statistics::from_node(&root));
+
+        article.html = Some(Util::serialize_node(&article_document, &root));
 
-        Ok(html)
+        Ok((article, root.get_content()))
     }
 
+    /// Cheap check whether `html` probably contains an article worth extracting,
+    /// without running the full algorithm. Index pages, login walls and search results
+    /// usually don't have enough long visible paragraphs.
+    pub fn is_probably_readerable(html: &str, options: &ReaderableOptions) -> bool {
+        match crate::FullTextParser::parse_html_string_patched(html) {
+            Ok(document) => Self::is_document_readerable(&document, options),
+            Err(error) => {
+                log::error!("Parsing HTML failed: {error:?}");
+                false
+            }
+        }
+    }
+
+    pub(crate) fn is_document_readerable(document: &Document, options: &ReaderableOptions) -> bool {
+        let Ok(xpath_ctx) = crate::FullTextParser::get_xpath_ctx(document) else {
+            return false;
+        };
+        // divs containing <br> are treated like paragraphs
+        let Ok(nodes) =
+            Util::evaluate_xpath(&xpath_ctx, "//p | //pre | //article | //div[br]", false)
+        else {
+            return false;
+        };
+
+        let mut score = 0.0;
+
+        nodes.iter().any(|node| {
+            if !Util::is_probably_visible(node) {
+                return false;
+            }
+
+            let match_string = Util::get_signature(node);
+            if constants::UNLIELY_CANDIDATES.is_match(&match_string)
+                && !constants::OKAY_MAYBE_ITS_A_CANDIDATE.is_match(&match_string)
+            {
+                return false;
+            }
+
+            if node.get_name().eq_ignore_ascii_case("p")
+                && Util::has_ancestor_tag(node, "li", Some(u64::MAX), None::<fn(&Node) -> bool>)
+            {
+                return false;
+            }
+
+            let text_length = node.get_content().trim().chars().count();
+            if text_length < options.min_content_length {
+                return false;
+            }
+
+            score += ((text_length - options.min_content_length) as f64).sqrt();
+            score > options.min_score
+        })
+    }
+
+    /// Extract the main content of `document` into `root`.
+    /// A detected byline is merged into the authors of `article`.
     pub(crate) fn extract_body(
+        document: Document,
+        root: &mut Node,
+        article: &mut Article,
+        options: &ReadabilityOptions,
+    ) -> Result<bool, FullTextParserError> {
+        Self::extract_body_with_state(document, root, article, options, &mut State::default())
+    }
+
+    fn extract_body_with_state(
+        document: Document,
+        root: &mut Node,
+        article: &mut Article,
+        options: &ReadabilityOptions,
+        state: &mut State,
+    ) -> Result<bool, FullTextParserError> {
+        let result = Self::grab_article(document, root, article.title.as_deref(), state, options);
+
+        if let Some(byline) = state.byline.as_deref() {
+            metadata::merge_byline(article, byline);
+        }
+
+        result
+    }
+
+    fn grab_article(
         document: Document,
         root: &mut Node,
         title: Option<&str>,
+        state: &mut State,
+        options: &ReadabilityOptions,
     ) -> Result<bool, FullTextParserError> {
-        let mut state = State::default();
         let mut document = document;
         let mut attempts: Vec<(Node, usize, Document)> = Vec::new();
         let document_cache = document
@@ -69,6 +285,14 @@ impl Readability {
             .map_err(|()| FullTextParserError::Readability)?;
 
         loop {
+            if let Some(trace) = state.trace.as_mut() {
+                trace.start_attempt(
+                    state.strip_unlikely,
+                    state.weigh_classes,
+                    state.clean_conditionally,
+                );
+            }
+
             let mut elements_to_score = Vec::new();
             let mut node: Option<Node> = document.clone().get_root_element();
 
@@ -88,7 +312,7 @@ impl Readability {
                     continue;
                 }
 
-                if Self::check_byline(node_ref, &match_string, &mut state) {
+                if Self::check_byline(node_ref, &match_string, state) {
                     node = Util::remove_and_next(node_ref);
                     continue;
                 }
@@ -242,7 +466,7 @@ impl Readability {
                 }
 
                 let inner_text = Util::get_inner_text(&element_to_score, true);
-                let inner_text_len = inner_text.len();
+                let inner_text_len = inner_text.chars().count();
 
                 // If this paragraph is less than 25 characters, don't even count it.
                 if inner_text_len < 25 {
@@ -261,10 +485,10 @@ impl Readability {
                 content_score += 1.0;
 
                 // Add points for any commas within this paragraph.
-                content_score += inner_text.split(',').count() as f64;
+                content_score += inner_text.split(constants::COMMAS).count() as f64;
 
                 // For every 100 characters in this paragraph, add another point. Up to 3 points.
-                content_score += f64::min(f64::floor(inner_text.len() as f64 / 100.0), 3.0);
+                content_score += f64::min(f64::floor(inner_text_len as f64 / 100.0), 3.0);
 
                 // Initialize and score ancestors.
                 for (level, mut ancestor) in ancestors.into_iter().enumerate() {
@@ -275,7 +499,7 @@ impl Readability {
                     }
 
                     if Self::get_content_score(&ancestor).is_none() {
-                        Self::initialize_node(&mut ancestor, &state)?;
+                        Self::initialize_node(&mut ancestor, state)?;
                         candidates.push(ancestor.clone());
                     }
 
@@ -312,8 +536,13 @@ impl Readability {
                 // should have a relatively small link density (5% or less) and be mostly
                 // unaffected by this operation.
                 if let Some(content_score) = Self::get_content_score(candidate) {
-                    let candidate_score = content_score * (1.0 - Util::get_link_density(candidate));
+                    let link_density = Util::get_link_density(candidate);
+                    let candidate_score = content_score * (1.0 - link_density);
                     Self::set_content_score(candidate, candidate_score)?;
+
+                    if let Some(trace) = state.trace.as_mut() {
+                        trace.candidate(candidate, content_score, link_density, candidate_score);
+                    }
                 }
             }
 
@@ -326,7 +555,10 @@ impl Readability {
                 }
             });
 
-            let top_candidates = candidates.into_iter().take(5).collect::<Vec<_>>();
+            let top_candidates = candidates
+                .into_iter()
+                .take(options.nb_top_candidates)
+                .collect::<Vec<_>>();
 
             for candidate in top_candidates.iter() {
                 log::debug!(
@@ -368,8 +600,7 @@ impl Readability {
 
                 root.add_child(&mut new_top_candidate).unwrap();
 
-                Self::initialize_node(&mut new_top_candidate, &state)
-                    .expect("init should not fail");
+                Self::initialize_node(&mut new_top_candidate, state).expect("init should not fail");
                 needed_to_create_top_candidate = true;
                 new_top_candidate
             };
@@ -417,7 +648,7 @@ impl Readability {
             }
 
             if Self::get_content_score(&top_candidate).is_none() {
-                Self::initialize_node(&mut top_candidate, &state)?;
+                Self::initialize_node(&mut top_candidate, state)?;
             }
 
             //Util::serialize_node(&top_candidate, "new_top_candidate.html");
@@ -485,7 +716,11 @@ impl Readability {
             }
 
             if Self::get_content_score(&top_candidate).is_none() {
-                Self::initialize_node(&mut top_candidate, &state)?;
+                Self::initialize_node(&mut top_candidate, state)?;
+            }
+
+            if let Some(trace) = state.trace.as_mut() {
+                trace.top_candidates(&document, &top_candidates, &top_candidate);
             }
 
             // Now that we have the top candidate, look through its siblings for content
@@ -543,7 +778,7 @@ impl Readability {
                         } else if sibling.get_name().to_uppercase() == "P" {
                             let link_density = Util::get_link_density(&sibling);
                             let node_content = Util::get_inner_text(&sibling, true);
-                            let node_length = node_content.len();
+                            let node_length = node_content.chars().count();
 
                             if node_length > 80
                                 && (link_density < 0.25
@@ -590,7 +825,7 @@ impl Readability {
             }
 
             if state.clean_conditionally {
-                crate::FullTextParser::post_process_page(&mut article_content)?;
+                crate::FullTextParser::post_process_page(&mut article_content, options)?;
             }
 
             if needed_to_create_top_candidate {
@@ -638,9 +873,12 @@ impl Readability {
             // finding the content, and the sieve approach gives us a higher likelihood of
             // finding the -right- content.
             let text = Util::get_inner_text(&article_content, true);
-            let text_length = text.len();
+            let text_length = text.chars().count();
+            if let Some(trace) = state.trace.as_mut() {
+                trace.text_length(text_length);
+            }
 
-            if text_length < constants::DEFAULT_CHAR_THRESHOLD {
+            if text_length < options.char_threshold {
                 parse_successful = false;
 
                 if state.strip_unlikely {
@@ -656,7 +894,19 @@ impl Readability {
                     attempts.push((article_content, text_length, document));
                     // No luck after removing flags, just return the longest text we found during the different loops
 
-                    attempts.sort_by(|(_, size_a, _), (_, size_b, _)| size_a.cmp(size_b));
+                    attempts.sort_by_key(|(_, size, _)| *size);
+
+                    if let Some(trace) = state.trace.as_mut() {
+                        // the sort is stable: the last of the longest attempts is used
+                        let longest = trace
+                            .trace
+                            .attempts
+                            .iter()
+                            .enumerate()
+                            .max_by_key(|(_, attempt)| attempt.text_length)
+                            .map(|(index, _)| index);
+                        trace.result(longest);
+                    }
 
                     // But first check if we actually have something
                     if let Some((best_attempt, _len, _document)) = attempts.pop() {
@@ -681,6 +931,11 @@ impl Readability {
                     .dup()
                     .map_err(|()| FullTextParserError::Readability)?;
             } else {
+                if let Some(trace) = state.trace.as_mut() {
+                    let index = trace.trace.attempts.len().checked_sub(1);
+                    trace.result(index);
+                }
+
                 for mut child in article_content.get_child_nodes() {
                     if child.is_null() {
                         continue;
@@ -739,13 +994,13 @@ impl Readability {
     // Check whether the input string could be a byline.
     // This verifies that the input length is less than 100 chars.
     fn is_valid_byline(line: &str) -> bool {
-        let len = line.trim().len();
+        let len = line.trim().chars().count();
         len > 0 && len < 100
     }
 
     // Initialize a node with the readability object. Also checks the
     // className/id for special names to add to its score.
-    fn initialize_node(node: &mut Node, state: &State) -> Result<(), FullTextParserError> {
+    fn initialize_node(node: &mut Node, state: &mut State) -> Result<(), FullTextParserError> {
         let score = match node.get_name().to_uppercase().as_str() {
             "DIV" => 5,
             "PRE" | "TD" | "BLOCKQUITE" => 3,
@@ -759,6 +1014,9 @@ impl Readability {
             0
         };
         let score = score + class_weight;
+        if let Some(trace) = state.trace.as_mut() {
+            trace.initialize(node, score as f64, class_weight as f64);
+        }
         log::debug!(
             "initialize node {} {}: {score}",
             node.get_name(),
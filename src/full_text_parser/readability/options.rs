@@ -0,0 +1,63 @@
+use crate::constants;
+
+/// Options of [`Readability`](super::Readability) mirroring the ones of Mozilla's Readability.js
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadabilityOptions {
+    /// Number of characters an article must have to be accepted. Shorter results make the
+    /// algorithm retry with less aggressive cleaning.
+    pub char_threshold: usize,
+    /// Number of top scoring candidates compared when looking for a better common ancestor
+    pub nb_top_candidates: usize,
+    /// Abort if the document has more elements than this. `None` parses documents of any size.
+    pub max_elems_to_parse: Option<usize>,
+    /// Classes kept on the content when `keep_classes` is disabled.
+    /// Mozilla's default is `["page"]`, empty to match the output of the site configs.
+    pub classes_to_preserve: Vec<String>,
+    /// Keep all classes of the content
+    pub keep_classes: bool,
+    /// Don't read metadata from JSON-LD
+    pub disable_json_ld: bool,
+    /// Added to the link density thresholds (0.2 and 0.5 depending on the class weight)
+    /// above which elements are removed while cleaning conditionally
+    pub link_density_modifier: f64,
+}
+
+impl Default for ReadabilityOptions {
+    fn default() -> Self {
+        Self {
+            char_threshold: constants::DEFAULT_CHAR_THRESHOLD,
+            nb_top_candidates: constants::DEFAULT_N_TOP_CANDIDATES,
+            max_elems_to_parse: None,
+            classes_to_preserve: Vec::new(),
+            keep_classes: false,
+            disable_json_ld: false,
+            link_density_modifier: 0.0,
+        }
+    }
+}
+
+/// Options of [`Readability::is_probably_readerable`](super::Readability::is_probably_readerable)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReaderableOptions {
+    /// Minimum number of characters a paragraph needs to count towards the score
+    pub min_content_length: usize,
+    /// Score the paragraphs have to exceed together
+    pub min_score: f64,
+}
+
+impl Default for ReaderableOptions {
+    fn default() -> Self {
+        Self {
+            min_content_length: 140,
+            min_score: 20.0,
+        }
+    }
+}
+
+/// Options of [`Readability::parse_with_trace`](super::Readability::parse_with_trace)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TraceOptions {
+    /// Keep a copy of the page with the candidates outlined & labeled for every pass.
+    /// Disabled by default since it serializes the whole document each time.
+    pub annotated_html: bool,
+}
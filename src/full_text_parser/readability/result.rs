@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+
+/// Result of [`Readability::parse`](super::Readability::parse)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReadabilityResult {
+    pub title: Option<String>,
+    /// Authors from the metadata or the byline found in the content
+    pub byline: Option<String>,
+    /// Text direction: `ltr`, `rtl` or `auto`
+    pub dir: Option<String>,
+    /// Language of the article as BCP 47 tag
+    pub lang: Option<String>,
+    /// HTML of the extracted content
+    pub content: String,
+    /// Text of the content without any markup
+    pub text_content: String,
+    /// Number of characters of `text_content`
+    pub length: usize,
+    /// Summary from the page metadata or the first substantial paragraph
+    pub excerpt: Option<String>,
+    pub site_name: Option<String>,
+    pub published_time: Option<DateTime<Utc>>,
+}
@@ -1,9 +1,12 @@
+use super::trace::TraceRecorder;
+
 pub struct State {
     pub strip_unlikely: bool,
     pub weigh_classes: bool,
     pub clean_conditionally: bool,
     pub should_remove_title_header: bool,
     pub byline: Option<String>,
+    pub trace: Option<TraceRecorder>,
 }
 
 impl Default for State {
@@ -14,6 +17,7 @@ impl Default for State {
             clean_conditionally: true,
             should_remove_title_header: true,
             byline: None,
+            trace: None,
         }
     }
 }
@@ -23,26 +23,20 @@ fn run_test(name: &str) {
     let xpath_ctx = crate::FullTextParser::get_xpath_ctx(&document).unwrap();
 
     crate::FullTextParser::prep_content(&xpath_ctx, None, &empty_config, &url, &document, None);
-    let mut article = Article {
-        title: None,
-        author: None,
-        url,
-        date: None,
-        thumbnail_url: None,
-        html: None,
-    };
+    let mut article = Article::new(url);
 
     let mut article_document = Document::new().unwrap();
     let mut root = Node::new("article", None, &document).unwrap();
     article_document.set_root_element(&root);
 
     metadata::extract(&xpath_ctx, None, None, &mut article);
-    super::Readability::extract_body(document, &mut root, article.title.as_deref()).unwrap();
+    super::Readability::extract_body(document, &mut root, &mut article, &Default::default())
+        .unwrap();
 
     let article_ctx = crate::FullTextParser::get_xpath_ctx(&article_document).unwrap();
 
     crate::FullTextParser::prevent_self_closing_tags(&article_ctx).unwrap();
-    crate::FullTextParser::post_process_document(&article_document).unwrap();
+    crate::FullTextParser::post_process_document(&article_document, &Default::default()).unwrap();
 
     let html = Util::serialize_node(&article_document, &root);
 
@@ -132,6 +126,26 @@ fn citylab_1() {
     run_test("citylab-1")
 }
 
+#[test]
+fn cjk_chinese() {
+    run_test("cjk-chinese")
+}
+
+#[test]
+fn cjk_japanese() {
+    run_test("cjk-japanese")
+}
+
+#[test]
+fn cjk_korean() {
+    run_test("cjk-korean")
+}
+
+#[test]
+fn cjk_short_blocks() {
+    run_test("cjk-short-blocks")
+}
+
 #[test]
 fn clean_links() {
     run_test("clean-links")
@@ -591,3 +605,168 @@ fn yahoo_4() {
 fn youth() {
     run_test("youth")
 }
+
+#[test]
+fn extract_article() {
+    let html = std::fs::read_to_string("./resources/tests/readability/001/source.html")
+        .expect("Failed to read source HTML");
+    let url = Url::parse("https://example.com/code/2013/").unwrap();
+
+    let article = super::Readability::extract_article(&html, Some(url)).unwrap();
+    assert_eq!(article.author.as_deref(), Some("Nicolas Perriault"));
+    assert!(article.statistics.unwrap().word_count > 0);
+    assert!(article.html.unwrap().starts_with("<article"));
+}
+
+#[test]
+fn extract_article_with_options() {
+    let html = std::fs::read_to_string("./resources/tests/readability/001/source.html")
+        .expect("Failed to read source HTML");
+
+    let options = super::ReadabilityOptions {
+        max_elems_to_parse: Some(10),
+        ..Default::default()
+    };
+    let result = super::Readability::extract_article_with(&html, None, &options);
+    assert!(matches!(
+        result,
+        Err(crate::full_text_parser::error::FullTextParserError::TooManyElements(_))
+    ));
+
+    let paragraph = "<p class=\"lead page\">Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.</p>";
+    let html = format!(
+        "<html><body><div class=\"content\">{}</div></body></html>",
+        paragraph.repeat(6)
+    );
+
+    let default = super::Readability::extract_article(&html, None).unwrap();
+    assert!(!default.html.unwrap().contains("class="));
+
+    let options = super::ReadabilityOptions {
+        keep_classes: true,
+        ..Default::default()
+    };
+    let article = super::Readability::extract_article_with(&html, None, &options).unwrap();
+    assert!(article.html.unwrap().contains(r#"class="lead page""#));
+
+    let options = super::ReadabilityOptions {
+        classes_to_preserve: vec!["page".into()],
+        ..Default::default()
+    };
+    let article = super::Readability::extract_article_with(&html, None, &options).unwrap();
+    let html = article.html.unwrap();
+    assert!(html.contains(r#"class="page""#));
+    assert!(!html.contains("lead"));
+}
+
+#[test]
+fn is_probably_readerable() {
+    let options = super::ReaderableOptions::default();
+
+    let html = std::fs::read_to_string("./resources/tests/readability/001/source.html")
+        .expect("Failed to read source HTML");
+    assert!(super::Readability::is_probably_readerable(&html, &options));
+
+    let paragraph = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam. ".repeat(2);
+
+    let article = format!("<p>{paragraph}</p><pre>{paragraph}</pre>");
+    assert!(super::Readability::is_probably_readerable(
+        &article, &options
+    ));
+
+    let article = format!("<div>{paragraph}<br>{paragraph}</div>");
+    assert!(super::Readability::is_probably_readerable(
+        &article, &options
+    ));
+
+    let strict = super::ReaderableOptions {
+        min_score: 100.0,
+        ..Default::default()
+    };
+    assert!(!super::Readability::is_probably_readerable(
+        &article, &strict
+    ));
+
+    let index = format!("<ul><li><p>{paragraph}</p></li><li><p>{paragraph}</p></li></ul>");
+    assert!(!super::Readability::is_probably_readerable(
+        &index, &options
+    ));
+
+    let hidden = format!("<p hidden>{paragraph}</p><p aria-hidden=\"true\">{paragraph}</p>");
+    assert!(!super::Readability::is_probably_readerable(
+        &hidden, &options
+    ));
+
+    let comments =
+        format!("<p class=\"comment\">{paragraph}</p><p class=\"comment\">{paragraph}</p>");
+    assert!(!super::Readability::is_probably_readerable(
+        &comments, &options
+    ));
+}
+
+#[test]
+fn parse() {
+    let html = std::fs::read_to_string("./resources/tests/readability/001/source.html")
+        .expect("Failed to read source HTML");
+
+    let result = super::Readability::parse(&html, None, &Default::default()).unwrap();
+    assert!(result.title.is_some());
+    assert_eq!(result.byline.as_deref(), Some("Nicolas Perriault"));
+    assert_eq!(result.lang.as_deref(), Some("en"));
+    assert!(result.content.starts_with("<article"));
+    assert!(result.length > 0);
+    assert_eq!(result.length, result.text_content.chars().count());
+    assert!(result.excerpt.is_some());
+}
+
+#[test]
+fn parse_with_trace() {
+    let html = std::fs::read_to_string("./resources/tests/readability/001/source.html")
+        .expect("Failed to read source HTML");
+
+    let trace_options = super::TraceOptions {
+        annotated_html: true,
+    };
+    let (result, trace) =
+        super::Readability::parse_with_trace(&html, None, &Default::default(), &trace_options)
+            .unwrap();
+    let untraced = super::Readability::parse(&html, None, &Default::default()).unwrap();
+    assert_eq!(result, untraced);
+    assert!(!result.content.contains("data-readability"));
+
+    let attempt = &trace.attempts[trace.result.unwrap()];
+    assert!(attempt.strip_unlikely);
+    assert!(attempt.text_length >= 500);
+    assert!(attempt
+        .top_candidate
+        .as_deref()
+        .unwrap()
+        .starts_with("/html/body"));
+    assert!(!attempt.top_candidates.is_empty());
+    assert!(attempt
+        .candidates
+        .windows(2)
+        .all(|pair| pair[0].score >= pair[1].score));
+
+    let annotated = trace.annotated_html().unwrap();
+    assert!(annotated.contains("data-readability-selected"));
+    assert!(annotated.contains(r#"data-readability-rank="1""#));
+
+    let (_result, scores_only) =
+        super::Readability::parse_with_trace(&html, None, &Default::default(), &Default::default())
+            .unwrap();
+    assert!(scores_only
+        .attempts
+        .iter()
+        .all(|attempt| attempt.annotated_html.is_none()));
+
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(
+            json["attempts"].as_array().unwrap().len(),
+            trace.attempts.len()
+        );
+        assert_eq!(json["result"], trace.result.unwrap());
+    }
+}
@@ -0,0 +1,240 @@
+use super::TraceOptions;
+use libxml::tree::{Document, Node, NodeType};
+use std::collections::HashMap;
+
+const SCORE_ATTR: &str = "data-readability-score";
+const RANK_ATTR: &str = "data-readability-rank";
+const SELECTED_ATTR: &str = "data-readability-selected";
+
+// outlines the candidates & labels them with their score
+const OVERLAY_STYLESHEET: &str = "[data-readability-score] { outline: 1px dashed #999; position: relative; }
+[data-readability-score]::before { content: attr(data-readability-score); position: absolute; top: 0; right: 0; z-index: 10000; padding: 0 2px; font: 11px monospace; color: #000; background: #ffd; }
+[data-readability-rank] { outline: 2px solid orange; }
+[data-readability-rank]::before { content: '#' attr(data-readability-rank) ' ' attr(data-readability-score); background: #fd8; }
+[data-readability-selected] { outline: 3px solid red; }
+[data-readability-selected]::before { background: #f88; }";
+
+/// Record of how [`Readability`](super::Readability) scored the page.
+/// Returned by [`Readability::parse_with_trace`](super::Readability::parse_with_trace).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReadabilityTrace {
+    /// One entry per pass over the document. Passes after the first one retry with
+    /// `strip_unlikely`, `weigh_classes` and `clean_conditionally` disabled one by one.
+    pub attempts: Vec<TraceAttempt>,
+    /// Index into `attempts` of the pass that produced the returned content
+    pub result: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceAttempt {
+    pub strip_unlikely: bool,
+    pub weigh_classes: bool,
+    pub clean_conditionally: bool,
+    /// All scored nodes, highest score first
+    pub candidates: Vec<TraceCandidate>,
+    /// Paths of the best `nb_top_candidates` candidates
+    pub top_candidates: Vec<String>,
+    /// Path of the node the content was taken from. Might be an ancestor of the best candidate.
+    pub top_candidate: Option<String>,
+    /// Characters of the content found in this pass
+    pub text_length: usize,
+    /// The document as scored in this pass with the candidates outlined and labeled.
+    /// Only recorded if [`TraceOptions::annotated_html`] is enabled.
+    pub annotated_html: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceCandidate {
+    /// XPath of the node in the document as modified for scoring, e.g. `/html/body/div[2]`
+    pub path: String,
+    /// Score of the tag name plus `class_weight`
+    pub initial_score: f64,
+    /// Weight of the class names & id. 0 if `weigh_classes` is disabled.
+    pub class_weight: f64,
+    /// Score after adding the scores of the contained paragraphs
+    pub propagated_score: f64,
+    pub link_density: f64,
+    /// `propagated_score` scaled by the link density
+    pub score: f64,
+}
+
+impl ReadabilityTrace {
+    /// Annotated HTML of the pass that produced the returned content
+    pub fn annotated_html(&self) -> Option<&str> {
+        self.result
+            .and_then(|index| self.attempts.get(index))
+            .and_then(|attempt| attempt.annotated_html.as_deref())
+    }
+}
+
+/// Collects the trace while `grab_article` runs
+pub(crate) struct TraceRecorder {
+    pub trace: ReadabilityTrace,
+    options: TraceOptions,
+    // initial score & class weight of each candidate
+    initial_scores: HashMap<Node, (f64, f64)>,
+}
+
+impl TraceRecorder {
+    pub fn new(options: &TraceOptions) -> Self {
+        Self {
+            trace: ReadabilityTrace::default(),
+            options: options.clone(),
+            initial_scores: HashMap::new(),
+        }
+    }
+
+    pub fn start_attempt(&mut self, strip_unlikely: bool, weigh_classes: bool, clean: bool) {
+        self.initial_scores.clear();
+        self.trace.attempts.push(TraceAttempt {
+            strip_unlikely,
+            weigh_classes,
+            clean_conditionally: clean,
+            ..Default::default()
+        });
+    }
+
+    pub fn initialize(&mut self, node: &Node, initial_score: f64, class_weight: f64) {
+        self.initial_scores
+            .insert(node.clone(), (initial_score, class_weight));
+    }
+
+    pub fn candidate(&mut self, node: &Node, propagated_score: f64, link_density: f64, score: f64) {
+        let (initial_score, class_weight) =
+            self.initial_scores.get(node).copied().unwrap_or_default();
+
+        if let Some(attempt) = self.trace.attempts.last_mut() {
+            attempt.candidates.push(TraceCandidate {
+                path: node_path(node),
+                initial_score,
+                class_weight,
+                propagated_score,
+                link_density,
+                score,
+            });
+        }
+    }
+
+    pub fn top_candidates(
+        &mut self,
+        document: &Document,
+        top_candidates: &[Node],
+        top_candidate: &Node,
+    ) {
+        let Some(attempt) = self.trace.attempts.last_mut() else {
+            return;
+        };
+
+        attempt
+            .candidates
+            .sort_by(|a, b| b.score.total_cmp(&a.score));
+        attempt.top_candidates = top_candidates.iter().map(node_path).collect();
+        attempt.top_candidate = Some(node_path(top_candidate));
+        if self.options.annotated_html {
+            attempt.annotated_html = Some(annotate(
+                document,
+                self.initial_scores.keys(),
+                top_candidates,
+                top_candidate,
+            ));
+        }
+    }
+
+    pub fn text_length(&mut self, text_length: usize) {
+        if let Some(attempt) = self.trace.attempts.last_mut() {
+            attempt.text_length = text_length;
+        }
+    }
+
+    pub fn result(&mut self, index: Option<usize>) {
+        self.trace.result = index;
+    }
+}
+
+// serialize the document with the candidates marked, then remove the marks again
+fn annotate<'a>(
+    document: &Document,
+    candidates: impl Iterator<Item = &'a Node>,
+    top_candidates: &'a [Node],
+    top_candidate: &'a Node,
+) -> String {
+    let mut marked = Vec::new();
+
+    for node in candidates.chain(top_candidates).chain([top_candidate]) {
+        let mut node = node.clone();
+        if node.get_parent().is_none() {
+            continue;
+        }
+
+        if let Some(score) = node
+            .get_attribute(crate::constants::SCORE_ATTR)
+            .and_then(|score| score.parse::<f64>().ok())
+        {
+            _ = node.set_attribute(SCORE_ATTR, &format!("{score:.1}"));
+        }
+        marked.push(node);
+    }
+    for (rank, node) in top_candidates.iter().enumerate() {
+        _ = node
+            .clone()
+            .set_attribute(RANK_ATTR, &(rank + 1).to_string());
+    }
+    _ = top_candidate.clone().set_attribute(SELECTED_ATTR, "");
+
+    let html = document.to_string();
+
+    for mut node in marked {
+        _ = node.remove_attribute(SCORE_ATTR);
+        _ = node.remove_attribute(RANK_ATTR);
+        _ = node.remove_attribute(SELECTED_ATTR);
+    }
+
+    let style = format!("<style>\n{OVERLAY_STYLESHEET}\n</style>\n");
+    match html.find("</head>") {
+        Some(index) => format!("{}{style}{}", &html[..index], &html[index..]),
+        None => format!("{style}{html}"),
+    }
+}
+
+/// XPath of `node` with positions for siblings of the same name, e.g. `/html/body/div[2]/p`
+fn node_path(node: &Node) -> String {
+    let mut parts = Vec::new();
+    let mut current = Some(node.clone());
+
+    while let Some(node) = current {
+        if node.get_type() != Some(NodeType::ElementNode) {
+            break;
+        }
+
+        let name = node.get_name().to_lowercase();
+        let parent = node.get_parent();
+        let siblings = parent
+            .as_ref()
+            .map(|parent| {
+                parent
+                    .get_child_elements()
+                    .into_iter()
+                    .filter(|sibling| sibling.get_name().eq_ignore_ascii_case(&name))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if siblings.len() > 1 {
+            let position = siblings
+                .iter()
+                .position(|sibling| sibling == &node)
+                .unwrap_or_default();
+            parts.push(format!("{name}[{}]", position + 1));
+        } else {
+            parts.push(name);
+        }
+
+        current = parent;
+    }
+
+    parts.reverse();
+    format!("/{}", parts.join("/"))
+}
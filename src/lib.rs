@@ -19,17 +19,48 @@
 //! This re-implementation tries to mimic the original as closely as possible.
 
 mod article;
+mod author;
 pub mod clean;
 mod constants;
+mod date_parser;
+mod epub;
 mod error;
+mod footnote;
 mod full_text_parser;
 mod image_object;
+mod markdown;
+mod mhtml;
+mod plain_text;
+mod renderer;
+mod resource;
+mod save;
+mod standalone;
+mod statistics;
 mod util;
 mod video_object;
+mod warc;
 
 pub use article::Article;
+pub use author::Author;
+pub use date_parser::DateParser;
+pub use epub::{Epub, EpubError, EpubOptions};
+pub use error::ScraperError;
+pub use footnote::Footnote;
 #[doc(hidden)]
 pub use full_text_parser::config::ConfigEntry as FtrConfigEntry;
 #[doc(hidden)]
+pub use full_text_parser::config::{ConfigDiagnostic, Severity as ConfigSeverity, XPathMatch};
+#[doc(hidden)]
 pub use full_text_parser::FullTextParser;
-pub use full_text_parser::Readability;
+pub use full_text_parser::{
+    Readability, ReadabilityOptions, ReadabilityResult, ReadabilityTrace, ReaderableOptions,
+    TraceAttempt, TraceCandidate, TraceOptions,
+};
+pub use markdown::MarkdownRenderer;
+pub use mhtml::{Mhtml, MhtmlError, MhtmlPart};
+pub use plain_text::{TextOptions, TextRenderer};
+pub use resource::Resource;
+pub use save::{Collision, SaveError, SaveOptions};
+pub use standalone::{StandaloneHtml, StandaloneOptions};
+pub use statistics::Statistics;
+pub use warc::{WarcError, WarcReader, WarcResponse};
@@ -0,0 +1,467 @@
+use crate::{
+    full_text_parser::error::FullTextParserError,
+    renderer::{self, BlockWriter},
+    util::Util,
+    FullTextParser,
+};
+use libxml::tree::Node;
+
+/// Serialize extracted article HTML as GitHub flavored Markdown
+///
+/// Besides the usual block and inline elements this understands the markup produced
+/// by the extractor itself: `<imageobject>` images, `videoWrapper` iframes and
+/// footnote markers & endnotes (rendered as `[^id]` and `[^id]: note`).
+pub struct MarkdownRenderer;
+
+impl MarkdownRenderer {
+    pub fn render(html: &str) -> Result<String, FullTextParserError> {
+        let document = FullTextParser::parse_html_string_patched(html).map_err(|error| {
+            log::error!("Parsing HTML failed: {error:?}");
+            FullTextParserError::Xml
+        })?;
+        let root = document
+            .get_root_element()
+            .ok_or(FullTextParserError::Xml)?;
+
+        Ok(Self::render_node(&root))
+    }
+
+    pub(crate) fn render_node(root: &Node) -> String {
+        let markdown = MarkdownWriter.blocks(root).join("\n\n");
+        if markdown.is_empty() {
+            markdown
+        } else {
+            markdown + "\n"
+        }
+    }
+}
+
+struct MarkdownWriter;
+
+impl MarkdownWriter {
+    fn blocks(&mut self, node: &Node) -> Vec<String> {
+        let mut blocks = Vec::new();
+        let mut inline = String::new();
+        self.walk(node, &mut blocks, &mut inline);
+        self.flush(&mut blocks, &mut inline);
+        blocks
+    }
+
+    // inline markdown of all children, block elements are joined by spaces
+    fn inline_content(&mut self, node: &Node) -> String {
+        let mut blocks = Vec::new();
+        let mut inline = String::new();
+        self.walk(node, &mut blocks, &mut inline);
+        blocks.push(inline);
+        blocks
+            .iter()
+            .map(|block| block.trim_matches('\n'))
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    // wrap `text` in `start` & `end` but keep surrounding whitespace outside of the markers
+    fn push_wrapped(inline: &mut String, text: &str, start: &str, end: &str) {
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            inline.push_str(text);
+            return;
+        }
+
+        if text.starts_with(' ') && !inline.ends_with(' ') {
+            inline.push(' ');
+        }
+        inline.push_str(start);
+        inline.push_str(trimmed);
+        inline.push_str(end);
+        if text.ends_with(' ') {
+            inline.push(' ');
+        }
+    }
+
+    fn push_escaped(inline: &mut String, c: char) {
+        let at_line_start = inline.is_empty() || inline.ends_with('\n');
+        if matches!(
+            c,
+            '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '|' | '~'
+        ) || (at_line_start && matches!(c, '#' | '-' | '+' | '='))
+        {
+            inline.push('\\');
+        }
+        inline.push(c);
+    }
+
+    fn image(node: &Node) -> String {
+        let Some(src) = node.get_attribute("src") else {
+            return String::new();
+        };
+        let alt = node
+            .get_attribute("alt")
+            .unwrap_or_default()
+            .replace(['[', ']'], "");
+        let alt = alt.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        match node.get_attribute("title") {
+            Some(title) if !title.trim().is_empty() => format!(
+                "![{alt}]({} \"{}\")",
+                Self::url(&src),
+                title.trim().replace('"', "\\\"")
+            ),
+            _ => format!("![{alt}]({})", Self::url(&src)),
+        }
+    }
+
+    // link around an image pointing to the image itself (e.g. `<imageobject>`)
+    fn is_image_link(node: &Node, href: &str) -> bool {
+        match node.get_child_elements().as_slice() {
+            [image] => {
+                image.get_name().to_uppercase() == "IMG"
+                    && image.get_attribute("src").as_deref().map(str::trim) == Some(href)
+                    && node.get_content().trim().is_empty()
+            }
+            _ => false,
+        }
+    }
+
+    fn media_source(node: &Node) -> Option<String> {
+        node.get_attribute("src")
+            .or_else(|| {
+                Util::get_elements_by_tag_name(node, "source")
+                    .iter()
+                    .find_map(|source| source.get_attribute("src"))
+            })
+            .map(|src| src.trim().to_string())
+            .filter(|src| !src.is_empty())
+    }
+
+    fn url(url: &str) -> String {
+        let url = url.trim();
+        if url.contains([' ', '(', ')']) {
+            format!("<{}>", url.replace(['<', '>'], ""))
+        } else {
+            url.to_string()
+        }
+    }
+
+    fn code_span(code: &str) -> String {
+        let code = code.replace('\n', " ");
+        if code.trim().is_empty() {
+            return String::new();
+        }
+
+        let fence = "`".repeat(Self::longest_backtick_run(&code) + 1);
+        if code.starts_with('`') || code.ends_with('`') {
+            format!("{fence} {code} {fence}")
+        } else {
+            format!("{fence}{code}{fence}")
+        }
+    }
+
+    fn code_block(node: &Node) -> String {
+        let code = node.get_content();
+        let code = code.trim_matches('\n').trim_end();
+
+        let language = std::iter::once(node.clone())
+            .chain(Util::get_elements_by_tag_name(node, "code"))
+            .flat_map(|node| node.get_class_names())
+            .find_map(|class| {
+                class
+                    .strip_prefix("language-")
+                    .or_else(|| class.strip_prefix("lang-"))
+                    .map(String::from)
+            })
+            .unwrap_or_default();
+
+        let fence = "`".repeat((Self::longest_backtick_run(code) + 1).max(3));
+        format!("{fence}{language}\n{code}\n{fence}")
+    }
+
+    fn longest_backtick_run(text: &str) -> usize {
+        text.split(|c| c != '`')
+            .map(|run| run.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn list(&mut self, node: &Node, ordered: bool) -> String {
+        let mut number = node
+            .get_attribute("start")
+            .and_then(|start| start.trim().parse::<i64>().ok())
+            .unwrap_or(1);
+        let mut items = Vec::new();
+
+        for item in node.get_child_elements() {
+            if item.get_name().to_uppercase() != "LI" {
+                continue;
+            }
+
+            let marker = if ordered {
+                format!("{number}. ")
+            } else {
+                "- ".to_string()
+            };
+            number += 1;
+
+            let indent = " ".repeat(marker.len());
+            let content = self.blocks(&item).join("\n");
+            items.push(renderer::indent(&content, &marker, &indent));
+        }
+
+        items.join("\n")
+    }
+
+    fn table(&mut self, node: &Node) -> String {
+        let rows = Util::get_elements_by_tag_name(node, "tr")
+            .into_iter()
+            .filter(|row| {
+                // skip rows of nested tables
+                Util::get_node_ancestors(row, None)
+                    .iter()
+                    .find(|ancestor| ancestor.get_name().to_uppercase() == "TABLE")
+                    == Some(node)
+            })
+            .map(|row| {
+                row.get_child_elements()
+                    .iter()
+                    .filter(|cell| matches!(cell.get_name().to_uppercase().as_str(), "TD" | "TH"))
+                    .map(|cell| {
+                        // cells can't span multiple lines
+                        self.inline_content(cell)
+                            .trim()
+                            .replace("\\\n", "<br>")
+                            .replace('\n', "<br>")
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|cells| !cells.is_empty())
+            .collect::<Vec<_>>();
+
+        let Some(columns) = rows.iter().map(Vec::len).max() else {
+            return String::new();
+        };
+
+        let format_row = |cells: &[String]| {
+            let mut row = String::from("|");
+            for column in 0..columns {
+                row.push(' ');
+                row.push_str(cells.get(column).map(String::as_str).unwrap_or_default());
+                row.push_str(" |");
+            }
+            row
+        };
+
+        let mut table = vec![
+            format_row(&rows[0]),
+            format!("|{}", " --- |".repeat(columns)),
+        ];
+        table.extend(rows[1..].iter().map(|cells| format_row(cells)));
+        table.join("\n")
+    }
+
+    fn endnotes(&mut self, node: &Node) -> String {
+        Util::get_elements_by_tag_name(node, "li")
+            .iter()
+            .filter_map(|note| {
+                let id = note.get_attribute("id")?;
+                let content = self.blocks(note).join("\n\n");
+                Some(renderer::indent(&content, &format!("[^{id}]: "), "    "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl BlockWriter for MarkdownWriter {
+    fn special_element(
+        &mut self,
+        node: &Node,
+        tag_name: &str,
+        blocks: &mut Vec<String>,
+        inline: &mut String,
+    ) -> bool {
+        let role = node.get_attribute("role").unwrap_or_default();
+
+        match tag_name {
+            "BR" => inline.push_str("\\\n"),
+            "IMG" => inline.push_str(&Self::image(node)),
+            "A" if role == "doc-noteref" => {
+                let id = node.get_attribute("href").unwrap_or_default();
+                inline.push_str(&format!("[^{}]", id.trim().trim_start_matches('#')));
+            }
+            "A" => {
+                let text = self.inline_content(node);
+                let href = node.get_attribute("href").unwrap_or_default();
+                let href = href.trim();
+
+                if href.is_empty()
+                    || href.starts_with("javascript:")
+                    || Self::is_image_link(node, href)
+                {
+                    inline.push_str(&text);
+                } else if text.trim().is_empty() {
+                    inline.push_str(&format!("<{href}>"));
+                } else {
+                    Self::push_wrapped(inline, &text, "[", &format!("]({})", Self::url(href)));
+                }
+            }
+            "STRONG" | "B" => Self::push_wrapped(inline, &self.inline_content(node), "**", "**"),
+            "EM" | "I" | "CITE" => Self::push_wrapped(inline, &self.inline_content(node), "*", "*"),
+            "DEL" | "S" | "STRIKE" => {
+                Self::push_wrapped(inline, &self.inline_content(node), "~~", "~~")
+            }
+            "CODE" | "KBD" | "SAMP" | "TT" => {
+                inline.push_str(&Self::code_span(&node.get_content()))
+            }
+            "IFRAME" | "VIDEO" | "AUDIO" => {
+                if let Some(src) = Self::media_source(node) {
+                    let label = if tag_name == "AUDIO" {
+                        "Audio"
+                    } else {
+                        "Video"
+                    };
+                    self.flush(blocks, inline);
+                    blocks.push(format!("[{label}]({})", Self::url(&src)));
+                }
+            }
+            "H1" | "H2" | "H3" | "H4" | "H5" | "H6" => {
+                self.flush(blocks, inline);
+                let level = tag_name[1..].parse::<usize>().unwrap_or(1);
+                let heading = self.inline_content(node).replace("\\\n", " ");
+                let heading = heading.trim();
+                if !heading.is_empty() {
+                    blocks.push(format!("{} {heading}", "#".repeat(level)));
+                }
+            }
+            "UL" | "OL" => {
+                self.flush(blocks, inline);
+                let list = self.list(node, tag_name == "OL");
+                if !list.is_empty() {
+                    blocks.push(list);
+                }
+            }
+            "BLOCKQUOTE" => {
+                self.flush(blocks, inline);
+                let quote = self.blocks(node).join("\n\n");
+                if !quote.is_empty() {
+                    blocks.push(renderer::indent(&quote, "> ", "> "));
+                }
+            }
+            "PRE" => {
+                self.flush(blocks, inline);
+                blocks.push(Self::code_block(node));
+            }
+            "HR" => {
+                self.flush(blocks, inline);
+                blocks.push("---".into());
+            }
+            "TABLE" => {
+                self.flush(blocks, inline);
+                let table = self.table(node);
+                if !table.is_empty() {
+                    blocks.push(table);
+                }
+            }
+            "FIGCAPTION" => {
+                self.flush(blocks, inline);
+                let caption = self.inline_content(node);
+                let caption = caption.trim();
+                if !caption.is_empty() {
+                    blocks.push(format!("*{caption}*"));
+                }
+            }
+            "SECTION" if role == "doc-endnotes" => {
+                self.flush(blocks, inline);
+                let notes = self.endnotes(node);
+                if !notes.is_empty() {
+                    blocks.push(notes);
+                }
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn flush(&mut self, blocks: &mut Vec<String>, inline: &mut String) {
+        let paragraph = inline
+            .split('\n')
+            .map(|line| line.trim_start())
+            .collect::<Vec<_>>()
+            .join("\n");
+        inline.clear();
+
+        let paragraph = paragraph.trim();
+        let paragraph = paragraph.strip_suffix('\\').unwrap_or(paragraph).trim_end();
+        if !paragraph.is_empty() {
+            blocks.push(paragraph.to_string());
+        }
+    }
+
+    fn push_char(&mut self, inline: &mut String, c: char) {
+        Self::push_escaped(inline, c);
+    }
+
+    fn ends_with_space(&self, inline: &str) -> bool {
+        inline.is_empty() || inline.ends_with([' ', '\n'])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MarkdownRenderer;
+
+    #[test]
+    fn blocks_and_inlines() {
+        let html = r##"<article>
+            <h2>Some <em>title</em></h2>
+            <p>Text with <strong>bold </strong>text, <code>code</code>, a <a href="https://example.com/a b">link</a>
+                and a note<sup><a href="#fn1" role="doc-noteref">1</a></sup>. Escape *this*.</p>
+            <ul><li>one</li><li>two<ol><li>three</li></ol></li></ul>
+            <pre><code class="language-rust">let a = 1;</code></pre>
+            <table><tr><th>a</th><th>b|c</th></tr><tr><td>1</td></tr></table>
+            <section role="doc-endnotes"><ol><li id="fn1" role="doc-endnote"><p>The note.</p></li></ol></section>
+        </article>"##;
+
+        let expected = r##"## Some *title*
+
+Text with **bold** text, `code`, a [link](<https://example.com/a b>) and a note[^fn1]. Escape \*this\*.
+
+- one
+- two
+  1. three
+
+```rust
+let a = 1;
+```
+
+| a | b\|c |
+| --- | --- |
+| 1 |  |
+
+[^fn1]: The note.
+"##;
+
+        assert_eq!(MarkdownRenderer::render(html).unwrap(), expected);
+    }
+
+    #[test]
+    fn extractor_elements() {
+        let html = r#"<article>
+            <imageobject><a href="https://example.com/a.jpg"><img src="https://example.com/a.jpg" alt="A [cat]"></a></imageobject>
+            <figure><img src="b.png" alt=""><figcaption>A caption</figcaption></figure>
+            <div class="videoWrapper"><iframe src="https://www.youtube.com/embed/abc"></iframe></div>
+        </article>"#;
+
+        let expected = r#"![A cat](https://example.com/a.jpg)
+
+![](b.png)
+
+*A caption*
+
+[Video](https://www.youtube.com/embed/abc)
+"#;
+
+        assert_eq!(MarkdownRenderer::render(html).unwrap(), expected);
+    }
+}
@@ -0,0 +1,14 @@
+use crate::full_text_parser::error::FullTextParserError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MhtmlError {
+    #[error("Malformed MHTML document")]
+    Format,
+    #[error("No HTML part found")]
+    NoContent,
+    #[error("IO Error")]
+    IO(#[from] std::io::Error),
+    #[error("Scrape Error")]
+    Scrape(#[from] FullTextParserError),
+}
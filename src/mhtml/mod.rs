@@ -0,0 +1,387 @@
+mod error;
+
+pub use self::error::MhtmlError;
+
+use crate::{article::Article, resource::Resource, util::Util, FullTextParser};
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
+use std::io::Cursor;
+use std::path::Path;
+use url::Url;
+
+// browsers wrap base64 at 76 characters and don't always pad
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Single resource of an MHTML document
+#[derive(Debug, Clone)]
+pub struct MhtmlPart {
+    /// Full `Content-Type` including parameters like the charset
+    pub content_type: String,
+    /// `Content-Location`: the original URL of the resource
+    pub location: Option<Url>,
+    /// `Content-ID` without angle brackets, referenced via `cid:` URLs
+    pub content_id: Option<String>,
+    /// Content with the transfer encoding removed
+    pub data: Vec<u8>,
+}
+
+impl MhtmlPart {
+    pub fn is_html(&self) -> bool {
+        let content_type = self.content_type.to_ascii_lowercase();
+        content_type.starts_with("text/html") || content_type.starts_with("application/xhtml")
+    }
+
+    /// Content decoded with the charset of the `Content-Type` or the document itself
+    pub fn text(&self) -> String {
+        Util::decode_html(&self.data, Some(&self.content_type))
+    }
+}
+
+/// Web page saved as MHTML ("Save page as" → "Webpage, Single File")
+///
+/// A `multipart/related` MIME message with the page as root part followed by the images,
+/// stylesheets & frames it references. Parts are decoded from `quoted-printable` or `base64`.
+pub struct Mhtml {
+    /// All parts in document order. The first HTML part is the page itself, unless the
+    /// `start` parameter of the message points to another one.
+    pub parts: Vec<MhtmlPart>,
+    root: usize,
+    snapshot_location: Option<Url>,
+}
+
+impl Mhtml {
+    pub fn open(path: &Path) -> Result<Self, MhtmlError> {
+        Self::parse(&std::fs::read(path)?)
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Self, MhtmlError> {
+        let mut cursor = Cursor::new(data);
+        let headers = Util::read_headers(&mut cursor)?;
+        let body = &data[cursor.position() as usize..];
+
+        let content_type = Util::find_header(&headers, "content-type").ok_or_else(|| {
+            log::error!("MHTML document without Content-Type");
+            MhtmlError::Format
+        })?;
+
+        let mut parts = Vec::new();
+        Self::parse_entity(&headers, body, &mut parts)?;
+
+        let start = parameter(content_type, "start").map(|start| strip_brackets(&start));
+        let root = start
+            .and_then(|start| {
+                parts
+                    .iter()
+                    .position(|part| part.content_id.as_deref() == Some(start.as_str()))
+            })
+            .or_else(|| parts.iter().position(MhtmlPart::is_html))
+            .ok_or(MhtmlError::NoContent)?;
+
+        // chrome stores the URL of the page in the message headers as well
+        let snapshot_location = Util::find_header(&headers, "snapshot-content-location")
+            .and_then(|location| Url::parse(location).ok());
+
+        Ok(Self {
+            parts,
+            root,
+            snapshot_location,
+        })
+    }
+
+    /// The saved page
+    pub fn root(&self) -> &MhtmlPart {
+        &self.parts[self.root]
+    }
+
+    /// Original URL of the saved page
+    pub fn url(&self) -> Option<Url> {
+        self.root()
+            .location
+            .clone()
+            .or_else(|| self.snapshot_location.clone())
+    }
+
+    /// Embedded content for `url`, either a `cid:` URL or the original location of a part.
+    /// Can be used as resolver for [`crate::StandaloneHtml`], [`crate::Epub`] or
+    /// [`Article::save_bundle`].
+    pub fn resource(&self, url: &Url) -> Option<Resource> {
+        let part = if url.scheme() == "cid" {
+            let content_id = percent_decode(url.path());
+            self.parts
+                .iter()
+                .find(|part| part.content_id.as_deref() == Some(content_id.as_str()))
+        } else {
+            self.parts.iter().find(|part| {
+                part.location
+                    .as_ref()
+                    .map(|location| location.as_str() == url.as_str())
+                    .unwrap_or(false)
+            })
+        }?;
+
+        Some(Resource::new(part.data.clone(), &part.content_type))
+    }
+
+    /// Extract the article of the saved page. `cid:` references of embedded parts are
+    /// replaced with their original location, so all images can be looked up via [`Mhtml::resource`].
+    pub fn article(&self, parser: &FullTextParser) -> Result<Article, MhtmlError> {
+        let mut html = self.root().text();
+
+        for part in &self.parts {
+            if let (Some(content_id), Some(location)) = (&part.content_id, &part.location) {
+                html = html.replace(&format!("cid:{content_id}"), location.as_str());
+            }
+        }
+
+        Ok(parser.parse_offline(vec![html], None, self.url())?)
+    }
+
+    fn parse_entity(
+        headers: &[(String, String)],
+        body: &[u8],
+        parts: &mut Vec<MhtmlPart>,
+    ) -> Result<(), MhtmlError> {
+        let content_type = Util::find_header(headers, "content-type")
+            .unwrap_or("text/plain")
+            .to_string();
+
+        if !content_type.to_ascii_lowercase().starts_with("multipart/") {
+            let encoding = Util::find_header(headers, "content-transfer-encoding")
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            let data = match encoding.as_str() {
+                "base64" => {
+                    let encoded = body
+                        .iter()
+                        .filter(|byte| !byte.is_ascii_whitespace())
+                        .copied()
+                        .collect::<Vec<_>>();
+                    match BASE64.decode(encoded) {
+                        Ok(data) => data,
+                        Err(error) => {
+                            // a single broken resource shouldn't fail the whole page
+                            log::warn!("Skipping part with invalid base64 content: {error}");
+                            return Ok(());
+                        }
+                    }
+                }
+                "quoted-printable" => decode_quoted_printable(body),
+                _ => body.to_vec(),
+            };
+
+            let location = Util::find_header(headers, "content-location")
+                .and_then(|location| Url::parse(location).ok());
+            let content_id = Util::find_header(headers, "content-id").map(strip_brackets);
+
+            parts.push(MhtmlPart {
+                content_type,
+                location,
+                content_id,
+                data,
+            });
+            return Ok(());
+        }
+
+        let boundary = parameter(&content_type, "boundary").ok_or_else(|| {
+            log::error!("Multipart content without boundary");
+            MhtmlError::Format
+        })?;
+        let delimiter = format!("--{boundary}");
+        let closing_delimiter = format!("{delimiter}--");
+
+        let mut current: Option<Vec<u8>> = None;
+        for line in body.split_inclusive(|byte| *byte == b'\n') {
+            let trimmed = line.trim_ascii_end();
+            let is_closing = trimmed == closing_delimiter.as_bytes();
+            if trimmed == delimiter.as_bytes() || is_closing {
+                if let Some(part) = current.take() {
+                    Self::parse_part(&part, parts)?;
+                }
+                if is_closing {
+                    break;
+                }
+                current = Some(Vec::new());
+            } else if let Some(part) = current.as_mut() {
+                part.extend_from_slice(line);
+            }
+        }
+
+        // tolerate a missing closing delimiter
+        if let Some(part) = current {
+            Self::parse_part(&part, parts)?;
+        }
+
+        Ok(())
+    }
+
+    fn parse_part(part: &[u8], parts: &mut Vec<MhtmlPart>) -> Result<(), MhtmlError> {
+        // the line break before the delimiter belongs to it
+        let part = part
+            .strip_suffix(b"\r\n")
+            .or_else(|| part.strip_suffix(b"\n"))
+            .unwrap_or(part);
+
+        let mut cursor = Cursor::new(part);
+        let headers = Util::read_headers(&mut cursor)?;
+        Self::parse_entity(&headers, &part[cursor.position() as usize..], parts)
+    }
+}
+
+// value of `name=value` in a header like `multipart/related; boundary="abc"`
+fn parameter(header: &str, name: &str) -> Option<String> {
+    header.split(';').skip(1).find_map(|parameter| {
+        let (key, value) = parameter.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+fn strip_brackets(value: &str) -> String {
+    value
+        .trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .to_string()
+}
+
+fn decode_quoted_printable(data: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(data.len());
+    let mut index = 0;
+
+    while index < data.len() {
+        let byte = data[index];
+        if byte != b'=' {
+            decoded.push(byte);
+            index += 1;
+            continue;
+        }
+
+        let rest = &data[index + 1..];
+        if rest.starts_with(b"\r\n") {
+            // soft line break
+            index += 3;
+        } else if rest.starts_with(b"\n") {
+            index += 2;
+        } else if let Some(value) = rest
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(value);
+            index += 3;
+        } else {
+            decoded.push(byte);
+            index += 1;
+        }
+    }
+
+    decoded
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let value = (bytes[index] == b'%')
+            .then(|| bytes.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match value {
+            Some(value) => {
+                decoded.push(value);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_quoted_printable, Mhtml};
+    use crate::FullTextParser;
+    use url::Url;
+
+    const MHTML: &str = "From: <Saved by Blink>\r
+Snapshot-Content-Location: https://example.com/cafe\r
+Subject: =?utf-8?Q?Caf=C3=A9?=\r
+MIME-Version: 1.0\r
+Content-Type: multipart/related;\r
+\ttype=\"text/html\";\r
+\tboundary=\"----MultipartBoundary--abc----\"\r
+\r
+\r
+------MultipartBoundary--abc----\r
+Content-Type: text/html\r
+Content-ID: <frame-1@mhtml.blink>\r
+Content-Transfer-Encoding: quoted-printable\r
+Content-Location: https://example.com/cafe\r
+\r
+<html><head><meta http-equiv=3D\"Content-Type\" content=3D\"text/html; charset=3DUTF-8\"=\r
+><title>Caf=C3=A9 culture</title></head><body><article><h1>Caf=C3=A9 culture</h1><p>Paris is=\r
+ known for its caf=C3=A9s, where people sit for hours reading the newspaper, talking with frie=\r
+nds and watching the world go by. The tradition goes back centuries.</p><img src=3D\"cid:image-1@=\r
+mhtml.blink\" alt=3D\"Caf=C3=A9\"><p>Many of the most famous caf=C3=A9s were meeting places of writ=\r
+ers, artists and philosophers who discussed their ideas over coffee and wine.</p></article></body=\r
+></html>\r
+------MultipartBoundary--abc----\r
+Content-Type: image/png\r
+Content-Transfer-Encoding: base64\r
+Content-ID: <image-1@mhtml.blink>\r
+Content-Location: https://example.com/images/cafe.png\r
+\r
+iVBORw0KGgo=\r
+------MultipartBoundary--abc------\r
+";
+
+    #[test]
+    fn quoted_printable() {
+        assert_eq!(
+            decode_quoted_printable(b"a=3Db=\r\nc =C3=A9=\nd"),
+            "a=bc éd".as_bytes()
+        );
+    }
+
+    #[test]
+    fn parse() {
+        let mhtml = Mhtml::parse(MHTML.as_bytes()).unwrap();
+        assert_eq!(mhtml.parts.len(), 2);
+        assert_eq!(
+            mhtml.url().map(String::from).as_deref(),
+            Some("https://example.com/cafe")
+        );
+        assert!(mhtml.root().text().contains("<title>Café culture</title>"));
+
+        let image = mhtml
+            .resource(&Url::parse("cid:image-1@mhtml.blink").unwrap())
+            .unwrap();
+        assert_eq!(image.mime_type, "image/png");
+        assert_eq!(image.data, b"\x89PNG\r\n\x1a\n");
+        assert!(mhtml
+            .resource(&Url::parse("https://example.com/images/cafe.png").unwrap())
+            .is_some());
+
+        let article = mhtml.article(&FullTextParser::new(None)).unwrap();
+        assert_eq!(article.title.as_deref(), Some("Café culture"));
+        let html = article.html.unwrap();
+        assert!(html.contains("https://example.com/images/cafe.png"));
+        assert!(html.contains("philosophers"));
+    }
+}
@@ -0,0 +1,370 @@
+use crate::{
+    constants,
+    full_text_parser::error::FullTextParserError,
+    renderer::{self, BlockWriter},
+    FullTextParser,
+};
+use libxml::tree::Node;
+
+/// Options for [`TextRenderer`]
+#[derive(Debug, Clone)]
+pub struct TextOptions {
+    /// Wrap lines longer than this many characters. `None` disables wrapping.
+    pub line_width: Option<usize>,
+    /// Replace links with numbered references (`text[1]`) and list the URLs at the end
+    pub link_references: bool,
+    /// Render images as `[Image: alt text]`
+    pub image_descriptions: bool,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            line_width: Some(80),
+            link_references: true,
+            image_descriptions: true,
+        }
+    }
+}
+
+/// Render extracted article HTML as structured plain text
+///
+/// Paragraphs are separated by empty lines, headings are underlined (`=` for `h1`,
+/// `-` for all others), list items get `*` or numbered markers, quotes are prefixed
+/// with `> ` and preformatted text is indented by four spaces. Footnote markers
+/// are rendered as `[label]`.
+pub struct TextRenderer;
+
+impl TextRenderer {
+    pub fn render(html: &str, options: &TextOptions) -> Result<String, FullTextParserError> {
+        let document = FullTextParser::parse_html_string_patched(html).map_err(|error| {
+            log::error!("Parsing HTML failed: {error:?}");
+            FullTextParserError::Xml
+        })?;
+        let root = document
+            .get_root_element()
+            .ok_or(FullTextParserError::Xml)?;
+
+        Ok(Self::render_node(&root, options))
+    }
+
+    pub(crate) fn render_node(root: &Node, options: &TextOptions) -> String {
+        let mut writer = TextWriter {
+            options,
+            links: Vec::new(),
+            width: options.line_width,
+        };
+
+        let mut text = writer.blocks(root, options.line_width).join("\n\n");
+
+        if !writer.links.is_empty() {
+            text.push_str("\n\n");
+            for (index, link) in writer.links.iter().enumerate() {
+                text.push_str(&format!("[{}] {link}\n", index + 1));
+            }
+        } else if !text.is_empty() {
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+struct TextWriter<'a> {
+    options: &'a TextOptions,
+    links: Vec<String>,
+    // line width of the blocks currently rendered
+    width: Option<usize>,
+}
+
+impl TextWriter<'_> {
+    fn blocks(&mut self, node: &Node, width: Option<usize>) -> Vec<String> {
+        let outer_width = std::mem::replace(&mut self.width, width);
+        let mut blocks = Vec::new();
+        let mut inline = String::new();
+        self.walk(node, &mut blocks, &mut inline);
+        self.flush(&mut blocks, &mut inline);
+        self.width = outer_width;
+        blocks
+    }
+
+    fn list(&mut self, node: &Node, width: Option<usize>, ordered: bool) -> String {
+        let mut number = node
+            .get_attribute("start")
+            .and_then(|start| start.trim().parse::<i64>().ok())
+            .unwrap_or(1);
+        let mut items = Vec::new();
+
+        for item in node.get_child_elements() {
+            if item.get_name().to_uppercase() != "LI" {
+                continue;
+            }
+
+            let marker = if ordered {
+                format!("{number}. ")
+            } else {
+                "* ".to_string()
+            };
+            number += 1;
+
+            let indent = " ".repeat(marker.chars().count());
+            // keep items compact: nested lists & paragraphs directly follow the first line
+            let content = self
+                .blocks(&item, Self::inner_width(width, indent.len()))
+                .join("\n");
+            items.push(renderer::indent(&content, &marker, &indent));
+        }
+
+        items.join("\n")
+    }
+
+    fn table(&mut self, node: &Node) -> String {
+        let mut rows = Vec::new();
+
+        for row in node.get_child_elements().into_iter().flat_map(|child| {
+            if child.get_name().to_uppercase() == "TR" {
+                vec![child]
+            } else {
+                child
+                    .get_child_elements()
+                    .into_iter()
+                    .filter(|row| row.get_name().to_uppercase() == "TR")
+                    .collect()
+            }
+        }) {
+            let cells = row
+                .get_child_elements()
+                .iter()
+                .filter(|cell| matches!(cell.get_name().to_uppercase().as_str(), "TD" | "TH"))
+                .map(|cell| Self::normalize_line(&self.blocks(cell, None).join(" ")))
+                .collect::<Vec<_>>();
+
+            if cells.iter().any(|cell| !cell.is_empty()) {
+                rows.push(cells.join(" | "));
+            }
+        }
+
+        rows.join("\n")
+    }
+
+    fn normalize_line(line: &str) -> String {
+        line.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn wrap(line: &str, width: Option<usize>) -> String {
+        let Some(width) = width else {
+            return line.to_string();
+        };
+
+        let mut wrapped = String::new();
+        let mut line_length = 0;
+
+        for word in line.split(' ').filter(|word| !word.is_empty()) {
+            let word_length = word.chars().count();
+            if line_length > 0 && line_length + 1 + word_length > width {
+                wrapped.push('\n');
+                line_length = 0;
+            } else if line_length > 0 {
+                wrapped.push(' ');
+                line_length += 1;
+            }
+            wrapped.push_str(word);
+            line_length += word_length;
+        }
+
+        wrapped
+    }
+
+    fn inner_width(width: Option<usize>, indent: usize) -> Option<usize> {
+        width.map(|width| {
+            width
+                .saturating_sub(indent)
+                .max(constants::TEXT_MIN_LINE_WIDTH)
+        })
+    }
+}
+
+impl BlockWriter for TextWriter<'_> {
+    fn special_element(
+        &mut self,
+        node: &Node,
+        tag_name: &str,
+        blocks: &mut Vec<String>,
+        inline: &mut String,
+    ) -> bool {
+        let width = self.width;
+
+        match tag_name {
+            "BR" => inline.push('\n'),
+            "IMG" => {
+                if self.options.image_descriptions {
+                    let alt = node.get_attribute("alt").unwrap_or_default();
+                    let alt = alt.trim();
+                    if alt.is_empty() {
+                        inline.push_str("[Image]");
+                    } else {
+                        inline.push_str(&format!("[Image: {alt}]"));
+                    }
+                }
+            }
+            "A" if node.get_attribute("role").as_deref() == Some("doc-noteref") => {
+                inline.push_str(&format!("[{}]", node.get_content().trim()));
+            }
+            "A" => {
+                self.walk(node, blocks, inline);
+
+                let href = node.get_attribute("href").unwrap_or_default();
+                let href = href.trim();
+                if self.options.link_references
+                    && !href.is_empty()
+                    && !href.starts_with('#')
+                    && !href.starts_with("javascript:")
+                {
+                    let index = match self.links.iter().position(|link| link == href) {
+                        Some(index) => index,
+                        None => {
+                            self.links.push(href.to_string());
+                            self.links.len() - 1
+                        }
+                    };
+                    inline.push_str(&format!("[{}]", index + 1));
+                }
+            }
+            "H1" | "H2" | "H3" | "H4" | "H5" | "H6" => {
+                self.flush(blocks, inline);
+
+                let mut heading = String::new();
+                self.walk(node, &mut Vec::new(), &mut heading);
+                let heading = Self::normalize_line(&heading.replace('\n', " "));
+                if !heading.is_empty() {
+                    let underline_char = if tag_name == "H1" { "=" } else { "-" };
+                    let length = heading.chars().count();
+                    let length = width.map(|width| length.min(width)).unwrap_or(length);
+                    blocks.push(format!("{heading}\n{}", underline_char.repeat(length)));
+                }
+            }
+            "UL" | "OL" => {
+                self.flush(blocks, inline);
+                let list = self.list(node, width, tag_name == "OL");
+                if !list.is_empty() {
+                    blocks.push(list);
+                }
+            }
+            "BLOCKQUOTE" => {
+                self.flush(blocks, inline);
+                let quote = self.blocks(node, Self::inner_width(width, 2)).join("\n\n");
+                if !quote.is_empty() {
+                    blocks.push(renderer::indent(&quote, "> ", "> "));
+                }
+            }
+            "PRE" => {
+                self.flush(blocks, inline);
+                let code = node.get_content();
+                let code = code.trim_matches('\n').trim_end();
+                if !code.is_empty() {
+                    blocks.push(renderer::indent(code, "    ", "    "));
+                }
+            }
+            "HR" => {
+                self.flush(blocks, inline);
+                blocks.push("* * *".into());
+            }
+            "TABLE" => {
+                self.flush(blocks, inline);
+                let table = self.table(node);
+                if !table.is_empty() {
+                    blocks.push(table);
+                }
+            }
+            "DD" => {
+                self.flush(blocks, inline);
+                let content = self.blocks(node, Self::inner_width(width, 4)).join("\n\n");
+                if !content.is_empty() {
+                    blocks.push(renderer::indent(&content, "    ", "    "));
+                }
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn flush(&mut self, blocks: &mut Vec<String>, inline: &mut String) {
+        let lines = inline
+            .split('\n')
+            .map(Self::normalize_line)
+            .map(|line| Self::wrap(&line, self.width))
+            .collect::<Vec<_>>();
+        inline.clear();
+
+        let paragraph = lines.join("\n");
+        let paragraph = paragraph.trim_matches('\n');
+        if !paragraph.is_empty() {
+            blocks.push(paragraph.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TextOptions, TextRenderer};
+
+    #[test]
+    fn structure() {
+        let html = r#"<article>
+            <h1>Title</h1>
+            <p>Some <b>bold</b> text with a <a href="https://example.com/">link</a>.<br>Second line.</p>
+            <ul><li>first</li><li>second<ol start="3"><li>nested</li></ol></li></ul>
+            <blockquote><p>Quote</p><p>More quote</p></blockquote>
+            <pre>fn main() {
+    println!("hi");
+}</pre>
+            <table><tr><th>a</th><th>b</th></tr><tr><td>1</td><td>2</td></tr></table>
+            <p><img src="a.jpg" alt="A cat"> and <a href="https://example.com/">again</a></p>
+        </article>"#;
+
+        let text = TextRenderer::render(html, &TextOptions::default()).unwrap();
+        let expected = r#"Title
+=====
+
+Some bold text with a link[1].
+Second line.
+
+* first
+* second
+  3. nested
+
+> Quote
+>
+> More quote
+
+    fn main() {
+        println!("hi");
+    }
+
+a | b
+1 | 2
+
+[Image: A cat] and again[1]
+
+[1] https://example.com/
+"#;
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn wrapping() {
+        let html = "<p>one two three four five six seven eight nine ten eleven twelve</p>";
+        let options = TextOptions {
+            line_width: Some(20),
+            link_references: false,
+            image_descriptions: false,
+        };
+
+        let text = TextRenderer::render(html, &options).unwrap();
+        assert_eq!(
+            text,
+            "one two three four\nfive six seven eight\nnine ten eleven\ntwelve\n"
+        );
+    }
+}
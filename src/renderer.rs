@@ -0,0 +1,89 @@
+use crate::constants;
+use libxml::tree::{Node, NodeType};
+
+/// Traversal shared by the plain text & Markdown renderers
+///
+/// Text is collected in `inline` with runs of whitespace collapsed to a single space.
+/// Block elements flush it to `blocks` before and after their content, elements
+/// in `SKIP_OUTPUT_ELEMS` are dropped and all other elements are walked through.
+pub(crate) trait BlockWriter {
+    /// Render elements that need special output. Returns `false` for all others.
+    fn special_element(
+        &mut self,
+        node: &Node,
+        tag_name: &str,
+        blocks: &mut Vec<String>,
+        inline: &mut String,
+    ) -> bool;
+
+    /// Move the paragraph collected in `inline` to `blocks`
+    fn flush(&mut self, blocks: &mut Vec<String>, inline: &mut String);
+
+    /// Append a non-whitespace character of a text node
+    fn push_char(&mut self, inline: &mut String, c: char) {
+        inline.push(c);
+    }
+
+    /// Whether whitespace at the end of `inline` would be redundant
+    fn ends_with_space(&self, inline: &str) -> bool {
+        inline.ends_with(' ')
+    }
+
+    fn walk(&mut self, node: &Node, blocks: &mut Vec<String>, inline: &mut String) {
+        for child in node.get_child_nodes() {
+            match child.get_type() {
+                Some(NodeType::TextNode) | Some(NodeType::CDataSectionNode) => {
+                    let content = child.get_content();
+                    let mut last_was_space = self.ends_with_space(inline);
+                    for c in content.chars() {
+                        if c.is_whitespace() {
+                            if !last_was_space {
+                                inline.push(' ');
+                            }
+                            last_was_space = true;
+                        } else {
+                            self.push_char(inline, c);
+                            last_was_space = false;
+                        }
+                    }
+                }
+                Some(NodeType::ElementNode) => self.element(&child, blocks, inline),
+                _ => {}
+            }
+        }
+    }
+
+    fn element(&mut self, node: &Node, blocks: &mut Vec<String>, inline: &mut String) {
+        let tag_name = node.get_name().to_uppercase();
+
+        if self.special_element(node, &tag_name, blocks, inline)
+            || constants::SKIP_OUTPUT_ELEMS.contains(tag_name.as_str())
+        {
+            return;
+        }
+
+        if constants::BLOCK_ELEMS.contains(tag_name.as_str()) {
+            self.flush(blocks, inline);
+            self.walk(node, blocks, inline);
+            self.flush(blocks, inline);
+        } else {
+            self.walk(node, blocks, inline);
+        }
+    }
+}
+
+// prefix the first line with `first` and all other non-empty lines with `rest`
+pub(crate) fn indent(text: &str, first: &str, rest: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let prefix = if index == 0 { first } else { rest };
+            if line.is_empty() {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{prefix}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
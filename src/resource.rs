@@ -0,0 +1,128 @@
+use crate::{constants, full_text_parser::error::FullTextParserError, util::Util, FullTextParser};
+use std::collections::HashMap;
+use url::Url;
+
+/// Content of a resource referenced by an article, e.g. an image.
+///
+/// Exporters that package or inline resources take a resolver closure
+/// `Fn(&Url) -> Option<Resource>`, which can look the resource up in a local cache
+/// or on disk. Resources the resolver returns `None` for are left out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resource {
+    pub data: Vec<u8>,
+    /// MIME type like `image/png`
+    pub mime_type: String,
+}
+
+impl Resource {
+    pub fn new(data: Vec<u8>, mime_type: &str) -> Self {
+        Self {
+            data,
+            mime_type: mime_type.trim().to_lowercase(),
+        }
+    }
+
+    /// MIME type without parameters like `; charset=utf-8`
+    pub fn essence(&self) -> &str {
+        self.mime_type
+            .split(';')
+            .next()
+            .map(str::trim)
+            .unwrap_or_default()
+    }
+
+    /// File extension typically used for the MIME type
+    pub fn extension(&self) -> &str {
+        match self.essence() {
+            "image/jpeg" | "image/jpg" | "image/pjpeg" => "jpg",
+            "image/png" => "png",
+            "image/gif" => "gif",
+            "image/webp" => "webp",
+            "image/avif" => "avif",
+            "image/svg+xml" => "svg",
+            "image/bmp" => "bmp",
+            "image/tiff" => "tiff",
+            "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+            "text/css" => "css",
+            "text/html" => "html",
+            "application/javascript" | "text/javascript" => "js",
+            "font/woff" => "woff",
+            "font/woff2" => "woff2",
+            "font/ttf" => "ttf",
+            "font/otf" => "otf",
+            "audio/mpeg" => "mp3",
+            "video/mp4" => "mp4",
+            _ => "bin",
+        }
+    }
+
+    pub fn is_image(&self) -> bool {
+        self.essence().starts_with("image/")
+    }
+
+    /// Replace the `src` & `srcset` URLs of all images in `html` with the value returned by
+    /// `replace` for the absolute image URL (e.g. a local path or a data URI).
+    /// `src` URLs that `replace` returns `None` for are made absolute, such `srcset`
+    /// candidates are dropped. Every distinct URL is only passed to `replace` once.
+    pub(crate) fn replace_image_sources<F>(
+        html: &str,
+        base_url: &Url,
+        mut replace: F,
+    ) -> Result<String, FullTextParserError>
+    where
+        F: FnMut(&Url) -> Option<String>,
+    {
+        let document = FullTextParser::parse_html_string_patched(html).map_err(|error| {
+            log::error!("Parsing HTML failed: {error:?}");
+            FullTextParserError::Xml
+        })?;
+        let context = FullTextParser::get_xpath_ctx(&document)?;
+        let mut replaced: HashMap<Url, Option<String>> = HashMap::new();
+        let mut lookup = |url: Url| -> Option<String> {
+            replaced
+                .entry(url)
+                .or_insert_with_key(|url| replace(url))
+                .clone()
+        };
+
+        let xpath = "//img[@src] | //img[@srcset] | //source[@srcset]";
+        for mut node in Util::evaluate_xpath(&context, xpath, false)? {
+            if let Some(src) = node.get_attribute("src") {
+                if let Ok(url) = base_url.join(src.trim()) {
+                    let new_src = lookup(url.clone()).unwrap_or_else(|| url.to_string());
+                    _ = node.set_attribute("src", &new_src);
+                }
+            }
+
+            if let Some(srcset) = node.get_attribute("srcset") {
+                let candidates = constants::SRC_SET_URL
+                    .captures_iter(&srcset)
+                    .filter_map(|captures| {
+                        let url = captures.get(1)?.as_str().trim_end_matches(',');
+                        let descriptor = captures.get(2).map_or("", |m| m.as_str());
+                        let new_url = lookup(base_url.join(url).ok()?)?;
+                        Some(format!("{new_url}{descriptor}"))
+                    })
+                    .collect::<Vec<_>>();
+
+                if !candidates.is_empty() {
+                    _ = node.set_attribute("srcset", &candidates.join(", "));
+                } else {
+                    _ = node.remove_attribute("srcset");
+                    _ = node.remove_attribute("sizes");
+                }
+            }
+        }
+
+        let body = document
+            .get_root_element()
+            .and_then(|root| Util::get_first_element_by_tag_name(&root, "body"))
+            .ok_or(FullTextParserError::Xml)?;
+
+        Ok(body
+            .get_child_nodes()
+            .iter()
+            .map(|child| document.node_to_string(child))
+            .collect())
+    }
+}
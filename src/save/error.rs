@@ -0,0 +1,16 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SaveError {
+    #[error("Article does not contain HTML")]
+    NoContent,
+    #[error("File already exists: {0}")]
+    Exists(PathBuf),
+    #[error("libXml Error")]
+    Xml,
+    #[error("Json Error")]
+    Json(#[from] serde_json::Error),
+    #[error("IO Error")]
+    IO(#[from] std::io::Error),
+}
@@ -0,0 +1,271 @@
+mod error;
+
+pub use self::error::SaveError;
+
+use crate::{article::Article, util::Util};
+use std::path::Path;
+
+// names reserved by windows regardless of the extension
+const RESERVED_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// What to do if a file of the same name already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Collision {
+    /// Append `-2`, `-3`, … to the name
+    #[default]
+    Rename,
+    Overwrite,
+    /// Return [`SaveError::Exists`]
+    Fail,
+}
+
+/// Options for [`Article::save`] and [`Article::save_bundle`]
+#[derive(Debug, Clone)]
+pub struct SaveOptions {
+    /// Prefix the name with the publication date, e.g. `2024-05-01-title`
+    pub date_prefix: bool,
+    /// Append a short hash of the article URL, e.g. `title-1a2b3c4d`
+    pub url_hash: bool,
+    /// Maximum number of characters of the slugified title
+    pub max_length: usize,
+    pub collision: Collision,
+    /// Write the article metadata as JSON next to the HTML
+    #[cfg(feature = "serde")]
+    pub metadata: bool,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            date_prefix: false,
+            url_hash: false,
+            max_length: 80,
+            collision: Collision::Rename,
+            #[cfg(feature = "serde")]
+            metadata: false,
+        }
+    }
+}
+
+impl SaveOptions {
+    /// File name without extension for `article`.
+    ///
+    /// The title (or the last segment of the URL if there is none) is converted to
+    /// lowercase, every run of characters that are neither letters nor digits is
+    /// replaced with a single `-` and the result is cut at a word boundary.
+    pub fn file_stem(&self, article: &Article) -> String {
+        let title = article.title.clone().unwrap_or_else(|| {
+            article
+                .url
+                .path_segments()
+                .and_then(|mut segments| segments.rfind(|segment| !segment.is_empty()))
+                .map(|segment| segment.split('.').next().unwrap_or_default().to_string())
+                .or_else(|| article.url.host_str().map(String::from))
+                .unwrap_or_default()
+        });
+
+        let mut stem = slugify(&title, self.max_length);
+
+        if self.date_prefix {
+            if let Some(date) = article.date {
+                stem = format!("{}-{stem}", date.format("%Y-%m-%d"));
+            }
+        }
+
+        if self.url_hash {
+            let hash = Util::stable_hash(article.url.as_str());
+            stem = format!("{stem}-{:08x}", hash as u32);
+        }
+
+        stem
+    }
+}
+
+pub(crate) fn slugify(text: &str, max_length: usize) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    if slug.chars().count() > max_length {
+        slug = slug.chars().take(max_length).collect();
+        // don't cut words in half if possible
+        if let Some(index) = slug.rfind('-') {
+            if slug[..index].chars().count() > max_length / 2 {
+                slug.truncate(index);
+            }
+        }
+    }
+
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        "article".into()
+    } else if RESERVED_NAMES.contains(&slug) {
+        format!("{slug}-article")
+    } else {
+        slug.into()
+    }
+}
+
+/// Apply the collision strategy to `stem`. All `files` (with `{}` as placeholder
+/// for the stem) inside `directory` must be available.
+pub(crate) fn available_stem(
+    directory: &Path,
+    stem: &str,
+    files: &[&str],
+    collision: Collision,
+) -> Result<String, SaveError> {
+    let taken = |stem: &str| {
+        files
+            .iter()
+            .map(|file| directory.join(file.replace("{}", stem)))
+            .find(|path| path.exists())
+    };
+
+    match collision {
+        Collision::Overwrite => Ok(stem.into()),
+        Collision::Fail => match taken(stem) {
+            Some(path) => Err(SaveError::Exists(path)),
+            None => Ok(stem.into()),
+        },
+        Collision::Rename => {
+            let mut candidate = stem.to_string();
+            let mut number = 1;
+            while taken(&candidate).is_some() {
+                number += 1;
+                candidate = format!("{stem}-{number}");
+            }
+            Ok(candidate)
+        }
+    }
+}
+
+/// All fields of the article except the HTML content
+#[cfg(feature = "serde")]
+pub(crate) fn metadata(article: &Article) -> Result<serde_json::Value, SaveError> {
+    let mut metadata = serde_json::to_value(article)?;
+    if let serde_json::Value::Object(fields) = &mut metadata {
+        fields.remove("html");
+    }
+    Ok(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{slugify, Collision, SaveError, SaveOptions};
+    use crate::{article::Article, resource::Resource};
+    use chrono::{TimeZone, Utc};
+    use url::Url;
+
+    #[test]
+    fn file_stem() {
+        assert_eq!(slugify("Rust: What's new?\n", 80), "rust-what-s-new");
+        assert_eq!(slugify("  ???  ", 80), "article");
+        assert_eq!(slugify("CON", 80), "con-article");
+        assert_eq!(slugify("Über die Straße 北京", 80), "über-die-straße-北京");
+        assert_eq!(slugify("one two three four", 12), "one-two");
+
+        let mut article = Article::new(Url::parse("https://example.com/news/story.html").unwrap());
+        let options = SaveOptions {
+            date_prefix: true,
+            url_hash: true,
+            ..Default::default()
+        };
+        assert_eq!(options.file_stem(&article), "story-9c0e3d18");
+
+        article.title = Some("A/B testing".into());
+        article.date = Some(Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap());
+        assert_eq!(
+            options.file_stem(&article),
+            "2024-05-01-a-b-testing-9c0e3d18"
+        );
+    }
+
+    #[test]
+    fn save() {
+        let directory =
+            std::env::temp_dir().join(format!("article-extractor-save-{}", std::process::id()));
+        _ = std::fs::remove_dir_all(&directory);
+
+        let mut article = Article::new(Url::parse("https://example.com/post").unwrap());
+        article.title = Some("Title".into());
+        assert!(matches!(
+            article.save(&directory, &SaveOptions::default()),
+            Err(SaveError::NoContent)
+        ));
+
+        article.html = Some(r#"<article><p>Text</p><img src="/a.png"></article>"#.into());
+        let options = SaveOptions {
+            #[cfg(feature = "serde")]
+            metadata: true,
+            ..Default::default()
+        };
+        let first = article.save(&directory, &options).unwrap();
+        let second = article.save(&directory, &options).unwrap();
+        assert_eq!(first, directory.join("title.html"));
+        assert_eq!(second, directory.join("title-2.html"));
+        #[cfg(feature = "serde")]
+        assert!(directory.join("title-2.json").exists());
+
+        let fail = SaveOptions {
+            collision: Collision::Fail,
+            ..Default::default()
+        };
+        assert!(matches!(
+            article.save(&directory, &fail),
+            Err(SaveError::Exists(_))
+        ));
+
+        let index = article
+            .save_bundle(&directory, &SaveOptions::default(), |_| {
+                Some(Resource::new(vec![1, 2, 3], "image/png"))
+            })
+            .unwrap();
+        assert_eq!(index, directory.join("title-3").join("index.html"));
+        let html = std::fs::read_to_string(&index).unwrap();
+        assert!(html.contains("<title>Title</title>"));
+        assert!(html.contains(r#"src="assets/image-1.png""#));
+        assert!(directory.join("title-3/assets/image-1.png").exists());
+
+        _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn save_html() {
+        let directory = std::env::temp_dir().join(format!(
+            "article-extractor-save-html-{}",
+            std::process::id()
+        ));
+        _ = std::fs::remove_dir_all(&directory);
+
+        let mut article = Article::new(Url::parse("https://example.com/post").unwrap());
+        article.title = Some("..\\C:\0 evil/..".into());
+        article.html = Some("<article><p>Text</p></article>".into());
+        article.save_html(&directory).unwrap();
+
+        let files = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(files, vec!["c-evil.html"]);
+        assert_eq!(
+            std::fs::read_to_string(directory.join("c-evil.html")).unwrap(),
+            "<article><p>Text</p></article>"
+        );
+
+        // the real IO error is returned if the directory can't be created
+        let blocked = directory.join("c-evil.html").join("sub");
+        let error = article.save_html(&blocked).unwrap_err();
+        assert_ne!(error.kind(), std::io::ErrorKind::NotFound);
+        assert!(!error.to_string().contains("HTML"));
+
+        _ = std::fs::remove_dir_all(&directory);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{article::Article, full_text_parser::error::FullTextParserError, resource::Resource};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::SecondsFormat;
+use url::Url;
+
+/// Options for [`StandaloneHtml`]
+#[derive(Debug, Clone)]
+pub struct StandaloneOptions {
+    /// CSS embedded into `<head>`. Defaults to [`StandaloneHtml::READER_STYLESHEET`].
+    pub stylesheet: Option<String>,
+}
+
+impl Default for StandaloneOptions {
+    fn default() -> Self {
+        Self {
+            stylesheet: Some(StandaloneHtml::READER_STYLESHEET.into()),
+        }
+    }
+}
+
+/// Render an article as a single self-contained HTML document for offline archiving
+///
+/// The `<head>` describes the article (title, author, dates, description, keywords and the
+/// canonical URL) and embeds the stylesheet. The body starts with the title, authors & date
+/// followed by the content. The sources of all images are replaced with `data:` URIs of the
+/// content returned by the resolver. Images it can't resolve keep their absolute remote URL.
+pub struct StandaloneHtml;
+
+impl StandaloneHtml {
+    /// Simple stylesheet for comfortable reading
+    pub const READER_STYLESHEET: &'static str = "body {
+  max-width: 42em;
+  margin: 0 auto;
+  padding: 1em;
+  font-family: Georgia, serif;
+  font-size: 1.1em;
+  line-height: 1.6;
+  color: #222;
+  background: #fff;
+}
+header .meta { color: #666; font-style: italic; }
+img, video { max-width: 100%; height: auto; }
+figure { margin: 1em 0; }
+figcaption { color: #666; font-size: 0.9em; }
+pre { overflow-x: auto; padding: 0.5em; background: #f4f4f4; }
+blockquote { margin-left: 0; padding-left: 1em; border-left: 3px solid #ccc; }
+table { border-collapse: collapse; }
+td, th { border: 1px solid #ccc; padding: 0.25em 0.5em; }
+";
+
+    pub fn render<R>(
+        article: &Article,
+        options: &StandaloneOptions,
+        resolver: R,
+    ) -> Result<String, FullTextParserError>
+    where
+        R: Fn(&Url) -> Option<Resource>,
+    {
+        let html = article.html.as_deref().ok_or(FullTextParserError::Scrape)?;
+
+        let content = Resource::replace_image_sources(html, &article.url, |url| {
+            let resource = resolver(url).filter(Resource::is_image)?;
+            Some(format!(
+                "data:{};base64,{}",
+                resource.essence(),
+                STANDARD.encode(&resource.data)
+            ))
+        })?;
+
+        Ok(Self::document(
+            article,
+            &content,
+            options.stylesheet.as_deref(),
+        ))
+    }
+
+    pub(crate) fn document(article: &Article, content: &str, stylesheet: Option<&str>) -> String {
+        let mut attributes = String::new();
+        if let Some(language) = article.language.as_deref() {
+            attributes.push_str(&format!(" lang=\"{}\"", escaper::encode_minimal(language)));
+        }
+        if let Some(direction) = article.direction.as_deref() {
+            attributes.push_str(&format!(" dir=\"{}\"", escaper::encode_minimal(direction)));
+        }
+
+        let title = article.title.as_deref().unwrap_or_default();
+        let date = |date: Option<chrono::DateTime<chrono::Utc>>| {
+            date.map(|date| date.to_rfc3339_opts(SecondsFormat::Secs, true))
+        };
+
+        let mut head = vec![
+            "<meta charset=\"utf-8\">".to_string(),
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">".to_string(),
+            format!("<title>{}</title>", escaper::encode_minimal(title)),
+        ];
+
+        let mut meta = |attribute: &str, name: &str, value: Option<String>| {
+            if let Some(value) = value {
+                head.push(format!(
+                    "<meta {attribute}=\"{name}\" content=\"{}\">",
+                    escaper::encode_minimal(&value)
+                ));
+            }
+        };
+        meta("name", "author", article.author.clone());
+        meta("name", "description", article.description.clone());
+        meta(
+            "name",
+            "keywords",
+            (!article.tags.is_empty()).then(|| article.tags.join(", ")),
+        );
+        meta("property", "og:site_name", article.site_name.clone());
+        meta("property", "article:published_time", date(article.date));
+        meta(
+            "property",
+            "article:modified_time",
+            date(article.date_modified),
+        );
+        meta("property", "article:section", article.section.clone());
+
+        let canonical_url = article.canonical_url.as_ref().unwrap_or(&article.url);
+        head.push(format!(
+            "<link rel=\"canonical\" href=\"{}\">",
+            escaper::encode_minimal(canonical_url.as_str())
+        ));
+
+        if let Some(stylesheet) = stylesheet {
+            // `</style>` would end the element early
+            head.push(format!(
+                "<style>\n{}\n</style>",
+                stylesheet.replace("</style", "<\\/style")
+            ));
+        }
+
+        let byline = [
+            article.author.clone(),
+            article.site_name.clone(),
+            article.date.map(|date| date.format("%Y-%m-%d").to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|part| escaper::encode_minimal(&part))
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+        let mut header = Vec::new();
+        if !title.is_empty() {
+            header.push(format!("<h1>{}</h1>", escaper::encode_minimal(title)));
+        }
+        if !byline.is_empty() {
+            header.push(format!("<p class=\"meta\">{byline}</p>"));
+        }
+        let header = if header.is_empty() {
+            String::new()
+        } else {
+            format!("<header>\n{}\n</header>\n", header.join("\n"))
+        };
+
+        format!(
+            "<!DOCTYPE html>\n<html{attributes}>\n<head>\n{}\n</head>\n<body>\n{header}{content}\n</body>\n</html>\n",
+            head.join("\n")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StandaloneHtml, StandaloneOptions};
+    use crate::{article::Article, resource::Resource};
+    use url::Url;
+
+    #[test]
+    fn inline_images() {
+        let mut article = Article::new(Url::parse("https://example.com/posts/1").unwrap());
+        article.title = Some("Cats & Dogs".into());
+        article.author = Some("Jane Doe".into());
+        article.tags = vec!["pets".into(), "animals".into()];
+        article.html = Some(
+            r#"<article><p>Text</p><img src="/a.png" srcset="/a.png 1x, /a-2x.png 2x, /missing.png 3x"><img src="missing.png"><picture><source srcset="/b.webp" type="image/webp"/><source srcset="/missing.webp"/><img src="/b.png"></picture></article>"#
+                .into(),
+        );
+
+        let html = StandaloneHtml::render(&article, &StandaloneOptions::default(), |url| match url
+            .path()
+        {
+            "/a.png" | "/a-2x.png" | "/b.png" => Some(Resource::new(b"png".to_vec(), "image/png")),
+            "/b.webp" => Some(Resource::new(b"webp".to_vec(), "image/webp")),
+            _ => None,
+        })
+        .unwrap();
+
+        assert!(html.contains("<title>Cats &amp; Dogs</title>"));
+        assert!(html.contains(r#"<meta name="author" content="Jane Doe">"#));
+        assert!(html.contains(r#"<meta name="keywords" content="pets, animals">"#));
+        assert!(html.contains(r#"<link rel="canonical" href="https://example.com/posts/1">"#));
+        assert!(html.contains("<style>"));
+        assert!(html.contains(
+            r#"src="data:image/png;base64,cG5n" srcset="data:image/png;base64,cG5n 1x, data:image/png;base64,cG5n 2x""#
+        ));
+        assert!(html.contains(r#"src="https://example.com/posts/missing.png""#));
+        assert!(html.contains(r#"srcset="data:image/webp;base64,d2VicA==""#));
+        assert!(!html.contains("missing.webp"));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{constants, util::Util};
+use libxml::tree::Node;
+use std::time::Duration;
+
+/// Text statistics of the extracted article
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Statistics {
+    /// Whitespace separated words. Chinese and Japanese characters count as one word each.
+    pub word_count: usize,
+    /// Characters of the text content with collapsed whitespace
+    pub character_count: usize,
+    /// Paragraphs containing text
+    pub paragraph_count: usize,
+    pub image_count: usize,
+    pub link_count: usize,
+    pub table_count: usize,
+}
+
+impl Statistics {
+    /// Average silent reading speed of adults
+    pub const DEFAULT_WORDS_PER_MINUTE: usize = constants::DEFAULT_WORDS_PER_MINUTE;
+
+    pub(crate) fn from_node(root: &Node) -> Self {
+        let text = Util::get_inner_text(root, true);
+
+        Self {
+            word_count: Self::count_words(&text),
+            character_count: text.chars().count(),
+            paragraph_count: Util::get_elements_by_tag_name(root, "p")
+                .iter()
+                .filter(|paragraph| !Util::get_inner_text(paragraph, true).is_empty())
+                .count(),
+            image_count: Util::get_elements_by_tag_name(root, "img").len(),
+            link_count: Util::get_elements_by_tag_name(root, "a")
+                .iter()
+                .filter(|link| link.has_attribute("href"))
+                .count(),
+            table_count: Util::get_elements_by_tag_name(root, "table").len(),
+        }
+    }
+
+    /// Estimated time to read the article at `words_per_minute`
+    /// (e.g. [`Statistics::DEFAULT_WORDS_PER_MINUTE`])
+    pub fn reading_time(&self, words_per_minute: usize) -> Duration {
+        if words_per_minute == 0 {
+            return Duration::ZERO;
+        }
+
+        let seconds = (self.word_count as f64 / words_per_minute as f64 * 60.0).ceil();
+        Duration::from_secs(seconds as u64)
+    }
+
+    /// Count words like [`Statistics::word_count`]
+    pub fn count_words(text: &str) -> usize {
+        let mut count = 0;
+
+        for token in text.split_whitespace() {
+            let mut in_word = false;
+            for c in token.chars() {
+                if !c.is_alphanumeric() {
+                    continue;
+                }
+
+                if Util::is_cjk(c) {
+                    count += 1;
+                    if in_word {
+                        count += 1;
+                        in_word = false;
+                    }
+                } else {
+                    in_word = true;
+                }
+            }
+
+            if in_word {
+                count += 1;
+            }
+        }
+
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Statistics;
+    use crate::FullTextParser;
+    use std::time::Duration;
+
+    #[test]
+    fn count_words() {
+        assert_eq!(Statistics::count_words("Hello, world - it's me!"), 4);
+        assert_eq!(Statistics::count_words("这是一个测试。"), 6);
+        assert_eq!(Statistics::count_words("Rust是一种语言"), 6);
+        assert_eq!(Statistics::count_words("한국어 문장입니다"), 2);
+    }
+
+    #[test]
+    fn from_node() {
+        let html = r#"<article>
+            <p>One two <a href="/three">three</a>.</p>
+            <p> </p>
+            <figure><img src="a.jpg"></figure>
+            <table><tr><td>four</td></tr></table>
+        </article>"#;
+
+        let document = FullTextParser::parse_html_string_patched(html).unwrap();
+        let root = document.get_root_element().unwrap();
+        let statistics = Statistics::from_node(&root);
+
+        assert_eq!(statistics.word_count, 4);
+        assert_eq!(statistics.paragraph_count, 1);
+        assert_eq!(statistics.image_count, 1);
+        assert_eq!(statistics.link_count, 1);
+        assert_eq!(statistics.table_count, 1);
+        assert_eq!(statistics.reading_time(2), Duration::from_secs(120));
+    }
+}
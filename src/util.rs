@@ -3,6 +3,7 @@ use libxml::{
     xpath::Context,
 };
 use std::fmt::Write;
+use std::io::BufRead;
 use std::{collections::HashSet, fs::DirEntry};
 
 use crate::{
@@ -266,6 +267,91 @@ impl Util {
         }
     }
 
+    // FNV-1a: unlike `DefaultHasher` the result is stable across rust versions
+    pub fn stable_hash(text: &str) -> u64 {
+        text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        })
+    }
+
+    /// Line without the trailing line break, `None` at the end of the input
+    pub fn read_line<R: BufRead + ?Sized>(reader: &mut R) -> std::io::Result<Option<String>> {
+        let mut line = Vec::new();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = String::from_utf8_lossy(&line);
+        Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+    }
+
+    /// `Name: value` header lines (HTTP, MIME, WARC) up to the next empty line
+    pub fn read_headers<R: BufRead + ?Sized>(
+        reader: &mut R,
+    ) -> std::io::Result<Vec<(String, String)>> {
+        let mut headers: Vec<(String, String)> = Vec::new();
+
+        while let Some(line) = Self::read_line(reader)? {
+            if line.is_empty() {
+                break;
+            }
+
+            if line.starts_with([' ', '\t']) {
+                // folded continuation of the previous header
+                if let Some((_, value)) = headers.last_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+            } else if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_string(), value.trim().to_string()));
+            }
+        }
+
+        Ok(headers)
+    }
+
+    /// Value of the first header called `name` (case-insensitive)
+    pub fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Decode raw HTML using the charset of the `Content-Type` header, a byte order mark
+    /// or a `<meta charset>` tag. Falls back to UTF-8 with replacement characters.
+    pub fn decode_html(data: &[u8], content_type: Option<&str>) -> String {
+        let sniff = &data[..data.len().min(constants::CHARSET_SNIFF_LENGTH)];
+        let sniff = String::from_utf8_lossy(sniff);
+        let label = content_type
+            .and_then(|content_type| constants::CHARSET.captures(content_type))
+            .or_else(|| constants::META_CHARSET.captures(&sniff))
+            .and_then(|captures| captures.get(1))
+            .map(|label| label.as_str());
+
+        let encoding = label
+            .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+            .unwrap_or(encoding_rs::UTF_8);
+
+        // a byte order mark overrides the declared encoding
+        let (text, _, _) = encoding.decode(data);
+        text.into_owned()
+    }
+
+    // Chinese characters, Japanese kana and full width CJK punctuation
+    pub fn is_cjk(c: char) -> bool {
+        matches!(c,
+            '\u{3000}'..='\u{303F}'
+            | '\u{3040}'..='\u{309F}'
+            | '\u{30A0}'..='\u{30FF}'
+            | '\u{31F0}'..='\u{31FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{2FA1F}'
+        )
+    }
+
     pub fn text_similarity(a: &str, b: &str) -> f64 {
         let a = a.to_lowercase();
         let b = b.to_lowercase();
@@ -466,7 +552,7 @@ impl Util {
     }
 
     pub fn get_link_density(node: &Node) -> f64 {
-        let text_length = Util::get_inner_text(node, true).len();
+        let text_length = Util::get_inner_text(node, true).chars().count();
         if text_length == 0 {
             return 0.0;
         }
@@ -482,7 +568,8 @@ impl Util {
                 } else {
                     1.0
                 };
-                link_length += Util::get_inner_text(&link_node, true).len() as f64 * coefficient;
+                link_length +=
+                    Util::get_inner_text(&link_node, true).chars().count() as f64 * coefficient;
             }
         }
 
@@ -596,7 +683,7 @@ impl Util {
 
     // Clean an element of all tags of type "tag" if they look fishy.
     // "Fishy" is an algorithm based on content length, classnames, link density, number of images & embeds, etc.
-    pub fn clean_conditionally(root: &mut Node, tag: &str) {
+    pub fn clean_conditionally(root: &mut Node, tag: &str, link_density_modifier: f64) {
         // Gather counts for other typical elements embedded within.
         // Traverse backwards so we can remove nodes at the same time
         // without effecting the traversal.
@@ -609,13 +696,13 @@ impl Util {
                 continue;
             }
 
-            if Self::should_remove(&node, tag) {
+            if Self::should_remove(&node, tag, link_density_modifier) {
                 node.unlink();
             }
         }
     }
 
-    fn should_remove(node: &Node, tag: &str) -> bool {
+    fn should_remove(node: &Node, tag: &str, link_density_modifier: f64) -> bool {
         // First check if this node IS data table, in which case don't remove it.
         let mut is_list = tag == "ul" || tag == "ol";
         if !is_list {
@@ -623,12 +710,13 @@ impl Util {
             let ul_nodes = Self::get_elements_by_tag_name(node, "ul");
             let ol_nodes = Self::get_elements_by_tag_name(node, "ol");
             for list_node in ul_nodes {
-                list_length += Util::get_inner_text(&list_node, false).len() as f64;
+                list_length += Util::get_inner_text(&list_node, false).chars().count() as f64;
             }
             for list_node in ol_nodes {
-                list_length += Util::get_inner_text(&list_node, false).len() as f64;
+                list_length += Util::get_inner_text(&list_node, false).chars().count() as f64;
             }
-            is_list = (list_length / Util::get_inner_text(node, false).len() as f64) > 0.9;
+            is_list =
+                (list_length / Util::get_inner_text(node, false).chars().count() as f64) > 0.9;
         }
 
         if tag == "table" && Self::is_data_table(node) {
@@ -649,7 +737,7 @@ impl Util {
             return true;
         }
 
-        if Self::get_char_count(node, ',') < 10 {
+        if Self::get_char_count(node, constants::COMMAS) < 10 {
             // If there are not very many commas, and the number of
             // non-paragraph elements is more than paragraphs or other
             // ominous signs, remove the element.
@@ -678,7 +766,7 @@ impl Util {
 
             let link_density = Self::get_link_density(node);
             let content = Self::get_inner_text(node, true);
-            let content_length = content.len();
+            let content_length = content.chars().count();
             let has_figure_ancestor =
                 Self::has_ancestor_tag(node, "figure", None, None::<fn(&Node) -> bool>);
 
@@ -698,8 +786,8 @@ impl Util {
                     && content_length < 25
                     && (img == 0 || img > 2)
                     && !has_figure_ancestor)
-                || (!is_list && weight < 25 && link_density > 0.2)
-                || (weight >= 25 && link_density > 0.5)
+                || (!is_list && weight < 25 && link_density > 0.2 + link_density_modifier)
+                || (weight >= 25 && link_density > 0.5 + link_density_modifier)
                 || ((embed_count == 1 && content_length < 75) || embed_count > 1);
 
             // Allow simple lists of images to remain in pages
@@ -752,12 +840,12 @@ impl Util {
         weight
     }
 
-    fn get_char_count(node: &Node, char: char) -> usize {
-        Util::get_inner_text(node, false).split(char).count() - 1
+    fn get_char_count(node: &Node, chars: &[char]) -> usize {
+        Util::get_inner_text(node, false).split(chars).count() - 1
     }
 
     fn get_text_density(node: &Node, tags: &[&str]) -> f64 {
-        let text_length = Util::get_inner_text(node, false).len();
+        let text_length = Util::get_inner_text(node, false).chars().count();
         if text_length == 0 {
             return 0.0;
         }
@@ -765,7 +853,7 @@ impl Util {
         let mut children_length = 0;
         for tag in tags {
             for child in Self::get_elements_by_tag_name(node, tag) {
-                children_length += Util::get_inner_text(&child, false).len()
+                children_length += Util::get_inner_text(&child, false).chars().count()
             }
         }
         children_length as f64 / text_length as f64
@@ -0,0 +1,16 @@
+use crate::full_text_parser::error::FullTextParserError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum WarcError {
+    #[error("Malformed WARC record")]
+    Format,
+    #[error("Malformed HTTP response")]
+    Http,
+    #[error("Url Error")]
+    Url(#[from] url::ParseError),
+    #[error("IO Error")]
+    IO(#[from] std::io::Error),
+    #[error("Scrape Error")]
+    Scrape(#[from] FullTextParserError),
+}
@@ -0,0 +1,372 @@
+mod error;
+
+pub use self::error::WarcError;
+
+use crate::{article::Article, util::Util, FullTextParser};
+use flate2::read::{DeflateDecoder, GzDecoder, MultiGzDecoder, ZlibDecoder};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::Path;
+use url::Url;
+
+/// HTTP response stored in a WARC `response` record
+#[derive(Debug, Clone)]
+pub struct WarcResponse {
+    /// `WARC-Target-URI` of the record
+    pub url: Url,
+    pub status: u16,
+    /// HTTP headers in their original order
+    pub headers: Vec<(String, String)>,
+    /// Body with transfer and content encoding removed
+    pub body: Vec<u8>,
+}
+
+impl WarcResponse {
+    /// Value of the first header called `name` (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        Util::find_header(&self.headers, name)
+    }
+
+    /// Responses without `Content-Type` are assumed to be HTML
+    pub fn is_html(&self) -> bool {
+        self.header("content-type")
+            .map(|content_type| {
+                let content_type = content_type.to_ascii_lowercase();
+                content_type.contains("text/html") || content_type.contains("application/xhtml")
+            })
+            .unwrap_or(true)
+    }
+
+    /// Body decoded with the charset of the `Content-Type` header or the document itself
+    pub fn text(&self) -> String {
+        Util::decode_html(&self.body, self.header("content-type"))
+    }
+}
+
+/// Reader for WARC archives, plain or gzip compressed (`.warc.gz`)
+///
+/// Iterates over all `response` records containing HTTP responses. Other records
+/// (`request`, `metadata`, `warcinfo`, …) are skipped. A malformed HTTP response only
+/// fails its own record, while a malformed or truncated WARC record ends the iteration.
+pub struct WarcReader<'a> {
+    reader: Box<dyn BufRead + 'a>,
+    done: bool,
+}
+
+impl WarcReader<'static> {
+    pub fn open(path: &Path) -> Result<Self, WarcError> {
+        Self::new(File::open(path)?)
+    }
+}
+
+impl<'a> WarcReader<'a> {
+    pub fn new<R: Read + 'a>(reader: R) -> Result<Self, WarcError> {
+        let mut reader = BufReader::new(reader);
+        let is_gzip = reader.fill_buf()?.starts_with(&[0x1f, 0x8b]);
+
+        // every record of a `.warc.gz` is a separate gzip member
+        let reader: Box<dyn BufRead + 'a> = if is_gzip {
+            Box::new(BufReader::new(MultiGzDecoder::new(reader)))
+        } else {
+            Box::new(reader)
+        };
+
+        Ok(Self {
+            reader,
+            done: false,
+        })
+    }
+
+    /// Extract the article of every successful HTML response.
+    ///
+    /// Records that can't be read are logged and skipped. Iterate over the
+    /// [`WarcReader`] itself to handle them.
+    pub fn articles<'p>(
+        self,
+        parser: &'p FullTextParser,
+    ) -> impl Iterator<Item = (Url, Result<Article, WarcError>)> + 'p
+    where
+        'a: 'p,
+    {
+        self.filter_map(|response| match response {
+            Ok(response) => Some(response),
+            Err(error) => {
+                log::error!("Failed to read WARC record: {error}");
+                None
+            }
+        })
+        .filter(|response| (200..300).contains(&response.status) && response.is_html())
+        .map(move |response| {
+            let article = parser
+                .parse_offline(vec![response.text()], None, Some(response.url.clone()))
+                .map_err(WarcError::from);
+            (response.url, article)
+        })
+    }
+
+    fn next_response(&mut self) -> Result<Option<WarcResponse>, WarcError> {
+        loop {
+            // records are separated by empty lines
+            let version = loop {
+                match Util::read_line(&mut self.reader)? {
+                    None => return Ok(None),
+                    Some(line) if line.trim().is_empty() => continue,
+                    Some(line) => break line,
+                }
+            };
+
+            if !version.starts_with("WARC/") {
+                log::error!("Expected WARC record, found '{version}'");
+                return Err(WarcError::Format);
+            }
+
+            let headers = Util::read_headers(&mut self.reader)?;
+            let length = Util::find_header(&headers, "content-length")
+                .and_then(|length| length.parse::<u64>().ok())
+                .ok_or(WarcError::Format)?;
+
+            let mut block = Vec::new();
+            (&mut self.reader).take(length).read_to_end(&mut block)?;
+            if block.len() as u64 != length {
+                log::error!("WARC record truncated");
+                return Err(WarcError::Format);
+            }
+
+            let is_response = Util::find_header(&headers, "warc-type") == Some("response");
+            let is_http = Util::find_header(&headers, "content-type")
+                .map(|content_type| content_type.starts_with("application/http"))
+                .unwrap_or(false);
+            if !is_response || !is_http {
+                continue;
+            }
+
+            // WARC 1.0 wraps the URI in angle brackets
+            let url = Util::find_header(&headers, "warc-target-uri")
+                .map(|url| url.trim_start_matches('<').trim_end_matches('>'))
+                .ok_or(WarcError::Format)?;
+
+            return Self::parse_http(Url::parse(url)?, block).map(Some);
+        }
+    }
+
+    fn parse_http(url: Url, block: Vec<u8>) -> Result<WarcResponse, WarcError> {
+        let mut cursor = Cursor::new(&block);
+
+        let status_line = Util::read_line(&mut cursor)?.ok_or(WarcError::Http)?;
+        let status = status_line
+            .strip_prefix("HTTP/")
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|status| status.parse::<u16>().ok())
+            .ok_or(WarcError::Http)?;
+
+        let headers = Util::read_headers(&mut cursor)?;
+        let mut body = block[cursor.position() as usize..].to_vec();
+
+        let is_chunked = Util::find_header(&headers, "transfer-encoding")
+            .map(|encoding| encoding.to_ascii_lowercase().contains("chunked"))
+            .unwrap_or(false);
+        if is_chunked {
+            // some crawlers store the decoded body but keep the header
+            match dechunk(&body) {
+                Some(dechunked) => body = dechunked,
+                None => log::debug!("Body of '{url}' isn't chunked"),
+            }
+        }
+
+        let content_encoding = Util::find_header(&headers, "content-encoding")
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        body = match content_encoding.as_str() {
+            "" | "identity" => body,
+            "gzip" | "x-gzip" => decode(GzDecoder::new(body.as_slice()))?,
+            "deflate" => decode(ZlibDecoder::new(body.as_slice()))
+                .or_else(|_| decode(DeflateDecoder::new(body.as_slice())))?,
+            encoding => {
+                log::warn!("Unsupported content encoding '{encoding}' of '{url}'");
+                body
+            }
+        };
+
+        Ok(WarcResponse {
+            url,
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+impl Iterator for WarcReader<'_> {
+    type Item = Result<WarcResponse, WarcError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.next_response();
+        if matches!(result, Err(WarcError::Format) | Err(WarcError::IO(_))) {
+            self.done = true;
+        }
+        result.transpose()
+    }
+}
+
+fn dechunk(body: &[u8]) -> Option<Vec<u8>> {
+    let mut cursor = Cursor::new(body);
+    let mut dechunked = Vec::new();
+
+    loop {
+        let line = Util::read_line(&mut cursor).ok()??;
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16).ok()?;
+        if size == 0 {
+            return Some(dechunked);
+        }
+
+        let start = cursor.position() as usize;
+        let end = start.checked_add(size)?;
+        let chunk = body.get(start..end)?;
+        dechunked.extend_from_slice(chunk);
+        cursor.set_position(end as u64);
+
+        // line break after the chunk
+        Util::read_line(&mut cursor).ok()??;
+    }
+}
+
+// a broken body only fails its own record: don't return an IO error
+fn decode<R: Read>(mut decoder: R) -> Result<Vec<u8>, WarcError> {
+    let mut data = Vec::new();
+    decoder.read_to_end(&mut data).map_err(|error| {
+        log::error!("Failed to decode HTTP body: {error}");
+        WarcError::Http
+    })?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dechunk, WarcError, WarcReader};
+    use crate::FullTextParser;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    fn record(warc_type: &str, content_type: &str, uri: &str, block: &[u8]) -> Vec<u8> {
+        let mut record = format!(
+            "WARC/1.1\r\nWARC-Type: {warc_type}\r\nWARC-Target-URI: {uri}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\r\n",
+            block.len()
+        )
+        .into_bytes();
+        record.extend_from_slice(block);
+        record.extend_from_slice(b"\r\n\r\n");
+        record
+    }
+
+    fn archive() -> Vec<Vec<u8>> {
+        let html = "<html><head><title>Caf\u{e9} culture</title></head><body><article><h1>Caf\u{e9} culture</h1><p>Paris is known for its caf\u{e9}s, where people sit for hours reading the newspaper, talking with friends and watching the world go by. The tradition goes back centuries.</p><p>Many of the most famous caf\u{e9}s were meeting places of writers, artists and philosophers who discussed their ideas over coffee and wine.</p></article></body></html>";
+        let latin1 = html.chars().map(|c| c as u8).collect::<Vec<_>>();
+
+        // chunked transfer encoding with a latin-1 body
+        let mut response =
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/html;\r\n charset=ISO-8859-1\r\nTransfer-Encoding: chunked\r\n\r\n"
+                .to_vec();
+        let (first, second) = latin1.split_at(100);
+        for chunk in [first, second] {
+            response.extend_from_slice(format!("{:x}\r\n", chunk.len()).as_bytes());
+            response.extend_from_slice(chunk);
+            response.extend_from_slice(b"\r\n");
+        }
+        response.extend_from_slice(b"0\r\n\r\n");
+
+        vec![
+            record(
+                "warcinfo",
+                "application/warc-fields",
+                "",
+                b"software: test\r\n",
+            ),
+            record(
+                "request",
+                "application/http; msgtype=request",
+                "https://example.com/cafe",
+                b"GET /cafe HTTP/1.1\r\nHost: example.com\r\n\r\n",
+            ),
+            record(
+                "response",
+                "application/http; msgtype=response",
+                "<https://example.com/old>",
+                b"HTTP/1.1 301 Moved Permanently\r\nLocation: /cafe\r\n\r\n",
+            ),
+            record(
+                "response",
+                "application/http; msgtype=response",
+                "https://example.com/cafe",
+                &response,
+            ),
+            record(
+                "response",
+                "application/http; msgtype=response",
+                "https://example.com/style.css",
+                b"HTTP/1.1 200 OK\r\nContent-Type: text/css\r\n\r\nbody {}",
+            ),
+        ]
+    }
+
+    #[test]
+    fn read_responses() {
+        let data = archive().concat();
+        let responses = WarcReader::new(data.as_slice())
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0].url.as_str(), "https://example.com/old");
+        assert_eq!(responses[0].status, 301);
+        assert_eq!(
+            responses[1].header("CONTENT-TYPE"),
+            Some("text/html; charset=ISO-8859-1")
+        );
+        assert!(responses[1].text().contains("<title>Café culture</title>"));
+        assert!(!responses[2].is_html());
+
+        // truncated record ends the iteration
+        let mut reader = WarcReader::new(&data[..data.len() - 20]).unwrap();
+        assert!(reader.by_ref().take(2).all(|response| response.is_ok()));
+        assert!(matches!(reader.next(), Some(Err(WarcError::Format))));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn gzip_articles() {
+        let data = archive()
+            .into_iter()
+            .flat_map(|record| {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&record).unwrap();
+                encoder.finish().unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let parser = FullTextParser::new(None);
+        let articles = WarcReader::new(data.as_slice())
+            .unwrap()
+            .articles(&parser)
+            .collect::<Vec<_>>();
+
+        assert_eq!(articles.len(), 1);
+        let (url, article) = &articles[0];
+        assert_eq!(url.as_str(), "https://example.com/cafe");
+        let article = article.as_ref().unwrap();
+        assert_eq!(article.title.as_deref(), Some("Café culture"));
+        assert!(article.html.as_deref().unwrap().contains("philosophers"));
+    }
+
+    #[test]
+    fn dechunk_oversized_chunk() {
+        assert_eq!(dechunk(b"3\r\nabc\r\n0\r\n\r\n"), Some(b"abc".to_vec()));
+        assert_eq!(dechunk(b"ffffffffffffffff\r\nabc\r\n0\r\n\r\n"), None);
+    }
+}